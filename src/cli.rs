@@ -1,15 +1,51 @@
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser)]
 pub struct Arguments {
     #[command(subcommand)]
     pub command: Command,
+    /// How errors are printed
+    #[arg(long, value_enum, default_value_t, global = true)]
+    pub message_format: crate::diagnostics::MessageFormat,
 }
 
 #[derive(Subcommand, Clone)]
 pub enum Command {
-    Format { directory: PathBuf },
-    Compile { input: PathBuf, output: PathBuf },
+    Format {
+        directory: PathBuf,
+    },
+    Compile {
+        input: PathBuf,
+        output: PathBuf,
+        #[command(flatten)]
+        options: CompileOptions,
+    },
+    /// Validates every schema and compiles it, without writing anything
+    Check {
+        input: PathBuf,
+        #[command(flatten)]
+        options: CompileOptions,
+    },
+}
+
+/// Options shared by the commands that compile the schemas
+#[derive(Args, Clone)]
+pub struct CompileOptions {
+    /// ProGuard or Tiny v2 mappings used to resolve the runtime names
+    #[arg(long)]
+    pub mappings: Option<PathBuf>,
+    /// How accessors read the original objects, unless their schema selects a mode
+    #[arg(long, value_enum, default_value_t)]
+    pub mode: crate::Mode,
+    /// How accessors read and write the fields of the original objects
+    #[arg(long, value_enum, default_value_t)]
+    pub field_access: crate::FieldAccess,
+    /// What is generated from the schemas
+    #[arg(long, value_enum, default_value_t)]
+    pub backend: crate::Backend,
+    /// Language of the generated accessors
+    #[arg(long, value_enum, default_value_t)]
+    pub lang: crate::Lang,
 }
@@ -4,6 +4,11 @@ const BODY: &str = r#"
 this.self = self;
 "#;
 
+// The object is kept by the accessor being extended
+const EXTENDED_BODY: &str = r#"
+super(self);
+"#;
+
 pub fn generate(object: &Object) -> java::Method {
     java::Method::builder()
         .is_constructor(true)
@@ -12,6 +17,10 @@ pub fn generate(object: &Object) -> java::Method {
             .name("self")
             .type_name("Object")
             .build()])
-        .body(BODY)
+        .body(if object.extends.is_some() {
+            EXTENDED_BODY
+        } else {
+            BODY
+        })
         .build()
 }
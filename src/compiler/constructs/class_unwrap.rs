@@ -0,0 +1,14 @@
+use crate::java;
+
+const BODY: &str = r#"
+return this.self;
+"#;
+
+/// Original object wrapped by the accessor
+pub fn generate() -> java::Method {
+    java::Method::builder()
+        .name("unwrap")
+        .return_type("Object")
+        .body(BODY)
+        .build()
+}
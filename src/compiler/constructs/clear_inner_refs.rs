@@ -4,6 +4,11 @@ const START_BODY: &str = r#"
 this.self = null;
 "#;
 
+// The inherited fields are cleared by the accessor being extended
+const EXTENDED_START_BODY: &str = r#"
+super.clearInnerRefs();
+"#;
+
 const CLEAR_SINGLE_REF: &str = r#"
 if (this.{{FIELD}} != null) this.{{FIELD}}.clearInnerRefs();
 "#;
@@ -13,43 +18,49 @@ clearInnerRefsArray(this.{{FIELD}});
 "#;
 
 fn field_is_enum(current: &Object, field: &crate::Field, includes: &[Object]) -> bool {
-    if let Some(name) = field.field_type.ends_in_object(current) {
-        if includes.iter().any(|x| x.name == name) {
-            return true;
-        }
-    }
+    let objects = field.field_type.objects(current);
 
-    false
+    !objects.is_empty()
+        && objects
+            .iter()
+            .all(|name| includes.iter().any(|x| x.is_named(name) && x.is_enum()))
 }
 
 pub fn generate(object: &Object, includes: &[Object]) -> java::Method {
     java::Method::builder()
         .name("clearInnerRefs")
         .body(
-            vec![START_BODY.to_owned()]
-                .into_iter()
-                .chain(
-                    object
-                        .fields
-                        .iter()
-                        .filter(|field| {
-                            !field.field_type.is_primitive()
-                                && !field_is_enum(object, field, includes)
-                        })
-                        .map(|field| {
-                            if field.field_type.is_nested_array()
-                                || matches!(field.field_type, crate::Type::Array(_))
-                            {
-                                CLEAR_ARRAY_REF
-                            } else {
-                                CLEAR_SINGLE_REF
-                            }
-                            .replace("{{FIELD}}", field.display_name())
-                        })
-                        .collect::<Vec<String>>(),
-                )
-                .collect::<Vec<String>>()
-                .join("\n"),
+            vec![if object.extends.is_some() {
+                EXTENDED_START_BODY
+            } else {
+                START_BODY
+            }
+            .to_owned()]
+            .into_iter()
+            .chain(
+                object
+                    .instance_fields()
+                    // Live accessors don't hold any other accessor
+                    .filter(|field| {
+                        !object.is_live()
+                            && !field.field_type.is_primitive()
+                            && !field_is_enum(object, field, includes)
+                    })
+                    .map(|field| {
+                        if field.field_type.is_nested_array()
+                            || field.field_type.is_collection()
+                            || matches!(field.field_type, crate::Type::Array(_))
+                        {
+                            CLEAR_ARRAY_REF
+                        } else {
+                            CLEAR_SINGLE_REF
+                        }
+                        .replace("{{FIELD}}", field.display_name())
+                    })
+                    .collect::<Vec<String>>(),
+            )
+            .collect::<Vec<String>>()
+            .join("\n"),
         )
         .build()
 }
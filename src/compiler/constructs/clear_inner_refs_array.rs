@@ -3,11 +3,28 @@ use crate::java;
 const BODY: &str = r#"
 if (obj == null) return;
 if (obj.getClass().isArray()) {
+    // Arrays of primitives (int[], byte[]...) can't hold accessors
+    if (obj.getClass().getComponentType().isPrimitive()) return;
+
     var array = (Object[])obj;
 
     for (var entry : array) {
         clearInnerRefsArray(entry);
     }
+} else if (obj instanceof Iterable) {
+    for (var entry : (Iterable<?>) obj) {
+        clearInnerRefsArray(entry);
+    }
+} else if (obj instanceof java.util.Map) {
+    for (var entry : ((java.util.Map<?, ?>) obj).entrySet()) {
+        clearInnerRefsArray(entry.getKey());
+        clearInnerRefsArray(entry.getValue());
+    }
+} else if (obj instanceof java.util.Optional) {
+    var optional = (java.util.Optional<?>) obj;
+    if (optional.isPresent()) clearInnerRefsArray(optional.get());
+} else if (obj instanceof String || obj instanceof Number || obj instanceof Boolean || obj instanceof Enum) {
+    return;
 } else {
     try {
         var method = obj.getClass().getMethod("clearInnerRefs");
@@ -0,0 +1,160 @@
+use crate::{java, Object, Type};
+
+const LIST_BODY: &str = r#"
+if (object == null) return null;
+
+var entries = new java.util.ArrayList<{{ELEMENT}}>();
+
+for (var entry : (Iterable<?>) object) {
+    entries.add({{ACCESSOR}});
+}
+
+return entries;
+"#;
+
+const SET_BODY: &str = r#"
+if (object == null) return null;
+
+var entries = new java.util.LinkedHashSet<{{ELEMENT}}>();
+
+for (var entry : (Iterable<?>) object) {
+    entries.add({{ACCESSOR}});
+}
+
+return entries;
+"#;
+
+const MAP_BODY: &str = r#"
+if (object == null) return null;
+
+var entries = new java.util.LinkedHashMap<{{KEY}}, {{VALUE}}>();
+
+for (var entry : ((java.util.Map<?, ?>) object).entrySet()) {
+    entries.put({{KEY_ACCESSOR}}, {{VALUE_ACCESSOR}});
+}
+
+return entries;
+"#;
+
+const ARRAY_BODY: &str = r#"
+if (object == null) return null;
+
+var array = (Object[]) object;
+var entries = {{NEW_ARRAY}};
+
+for (var i = 0; i < array.length; i++) {
+    entries[i] = {{ACCESSOR}};
+}
+
+return entries;
+"#;
+
+const OPTIONAL_BODY: &str = r#"
+if (object == null) return null;
+
+var optional = (java.util.Optional<?>) object;
+
+if (!optional.isPresent()) return java.util.Optional.empty();
+
+return java.util.Optional.ofNullable({{ACCESSOR}});
+"#;
+
+/// Java expression creating an array of `length` elements of `element`.
+/// Generic arrays can't be created directly, so the array is created with the erased type and
+/// then cast, e.g. `(java.util.List<FooAccessor>[]) new java.util.List[length]`.
+fn new_array(element: &str, length: &str) -> String {
+    let mut erased = String::new();
+    let mut depth = 0;
+    for c in element.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            c if depth == 0 => erased.push(c),
+            _ => {}
+        }
+    }
+
+    let (base, dimensions) = erased.split_at(erased.find('[').unwrap_or(erased.len()));
+    let new = format!("new {base}[{length}]{dimensions}");
+
+    if erased == element {
+        new
+    } else {
+        format!("({element}[]) {new}")
+    }
+}
+
+fn body(collection: &Type, object: &Object) -> String {
+    match collection {
+        Type::Array(inner) => ARRAY_BODY
+            .replace(
+                "{{NEW_ARRAY}}",
+                &new_array(&inner.java_name(object), "array.length"),
+            )
+            .replace("{{ACCESSOR}}", &inner.access_expression("array[i]", object)),
+        Type::List(inner) => LIST_BODY
+            .replace("{{ELEMENT}}", &inner.boxed_java_name(object))
+            .replace("{{ACCESSOR}}", &inner.access_expression("entry", object)),
+        Type::Set(inner) => SET_BODY
+            .replace("{{ELEMENT}}", &inner.boxed_java_name(object))
+            .replace("{{ACCESSOR}}", &inner.access_expression("entry", object)),
+        Type::Map(key, value) => MAP_BODY
+            .replace("{{KEY}}", &key.boxed_java_name(object))
+            .replace("{{VALUE}}", &value.boxed_java_name(object))
+            .replace(
+                "{{KEY_ACCESSOR}}",
+                &key.access_expression("entry.getKey()", object),
+            )
+            .replace(
+                "{{VALUE_ACCESSOR}}",
+                &value.access_expression("entry.getValue()", object),
+            ),
+        Type::Optional(inner) => OPTIONAL_BODY.replace(
+            "{{ACCESSOR}}",
+            &inner.access_expression("optional.get()", object),
+        ),
+        _ => unreachable!("Only collections and arrays have a collection accessor"),
+    }
+}
+
+/// Generates one `access{Collection}` method for every distinct collection or nested array type
+/// used by the object, wrapping the elements through their accessors.
+pub fn generate(object: &Object) -> Vec<java::Method> {
+    let mut generated: Vec<String> = vec![];
+    let mut methods = vec![];
+
+    for collection in object.types().flat_map(|x| x.access_methods()) {
+        let name = format!("access{}", collection.mangled_name(object));
+        if generated.contains(&name) {
+            continue;
+        }
+
+        methods.push(
+            java::Method::builder()
+                .is_static(true)
+                .visibility(java::Visibility::Private)
+                .exceptions(
+                    vec![
+                        "NoSuchFieldException",
+                        "SecurityException",
+                        "IllegalArgumentException",
+                        "IllegalAccessException",
+                    ]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                )
+                .name(&name)
+                .arguments(vec![java::Argument::builder()
+                    .name("object")
+                    .type_name("Object")
+                    .build()])
+                .return_type(collection.java_name(object))
+                .body(body(collection, object))
+                .build(),
+        );
+        generated.push(name);
+    }
+
+    methods
+}
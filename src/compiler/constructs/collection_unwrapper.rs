@@ -0,0 +1,139 @@
+use crate::{java, Object, Type};
+
+const ARRAY_BODY: &str = r#"
+if (object == null) return null;
+
+// Arrays of objects not bound to an original class fall back to Object[]
+var componentType = type == null ? Object.class : type.getComponentType();
+var entries = java.lang.reflect.Array.newInstance(componentType, object.length);
+
+for (var i = 0; i < object.length; i++) {
+    java.lang.reflect.Array.set(entries, i, {{UNWRAP}});
+}
+
+return entries;
+"#;
+
+const LIST_BODY: &str = r#"
+if (object == null) return null;
+
+var entries = new java.util.ArrayList<Object>();
+
+for (var entry : object) {
+    entries.add({{UNWRAP}});
+}
+
+return entries;
+"#;
+
+const SET_BODY: &str = r#"
+if (object == null) return null;
+
+var entries = new java.util.LinkedHashSet<Object>();
+
+for (var entry : object) {
+    entries.add({{UNWRAP}});
+}
+
+return entries;
+"#;
+
+const MAP_BODY: &str = r#"
+if (object == null) return null;
+
+var entries = new java.util.LinkedHashMap<Object, Object>();
+
+for (var entry : object.entrySet()) {
+    var key = entry.getKey();
+    var value = entry.getValue();
+    entries.put({{KEY_UNWRAP}}, {{VALUE_UNWRAP}});
+}
+
+return entries;
+"#;
+
+const OPTIONAL_BODY: &str = r#"
+if (object == null) return null;
+
+if (!object.isPresent()) return java.util.Optional.empty();
+
+var entry = object.get();
+return java.util.Optional.ofNullable({{UNWRAP}});
+"#;
+
+fn body(collection: &Type, object: &Object, includes: &[Object]) -> String {
+    match collection {
+        Type::Array(inner) => ARRAY_BODY.replace(
+            "{{UNWRAP}}",
+            &inner.unwrap_expression("object[i]", Some("componentType"), object, includes),
+        ),
+        Type::List(inner) => LIST_BODY.replace(
+            "{{UNWRAP}}",
+            &inner.unwrap_expression("entry", None, object, includes),
+        ),
+        Type::Set(inner) => SET_BODY.replace(
+            "{{UNWRAP}}",
+            &inner.unwrap_expression("entry", None, object, includes),
+        ),
+        Type::Map(key, value) => MAP_BODY
+            .replace(
+                "{{KEY_UNWRAP}}",
+                &key.unwrap_expression("key", None, object, includes),
+            )
+            .replace(
+                "{{VALUE_UNWRAP}}",
+                &value.unwrap_expression("value", None, object, includes),
+            ),
+        Type::Optional(inner) => OPTIONAL_BODY.replace(
+            "{{UNWRAP}}",
+            &inner.unwrap_expression("entry", None, object, includes),
+        ),
+        _ => unreachable!("Only collections and arrays have a collection unwrapper"),
+    }
+}
+
+/// Generates one `unwrap{Collection}` method for every distinct collection or array type holding
+/// accessors used by the object. They build a new collection with the original values of
+/// the accessors, so it can be written back into the original object.
+pub fn generate(object: &Object, includes: &[Object]) -> Vec<java::Method> {
+    let mut generated: Vec<String> = vec![];
+    let mut methods = vec![];
+
+    for collection in object.types().flat_map(|x| x.unwrap_methods()) {
+        let name = format!("unwrap{}", collection.mangled_name(object));
+        if generated.contains(&name) {
+            continue;
+        }
+
+        let mut arguments = vec![java::Argument::builder()
+            .name("object")
+            .type_name(collection.java_name(object))
+            .build()];
+
+        // Arrays need the class of the original array to create one of the same type
+        if let Type::Array(_) = collection {
+            arguments.push(
+                java::Argument::builder()
+                    .name("type")
+                    .type_name("Class<?>")
+                    .build(),
+            );
+        }
+
+        methods.push(
+            java::Method::builder()
+                .is_static(true)
+                .visibility(java::Visibility::Private)
+                .name(&name)
+                .arguments(arguments)
+                .return_type("Object")
+                // Arrays inside of collections load the original class of their elements
+                .exceptions(vec!["ClassNotFoundException".to_string()])
+                .body(body(collection, object, includes))
+                .build(),
+        );
+        generated.push(name);
+    }
+
+    methods
+}
@@ -0,0 +1,18 @@
+use crate::{java, Object};
+
+const BODY: &str = r#"
+this.names = names;
+"#;
+
+pub fn generate(object: &Object) -> java::Method {
+    java::Method::builder()
+        .is_constructor(true)
+        .visibility(java::Visibility::Private)
+        .name(format!("{}Accessor", object.display_name()))
+        .arguments(vec![java::Argument::builder()
+            .name("names")
+            .type_name("String...")
+            .build()])
+        .body(BODY)
+        .build()
+}
@@ -0,0 +1,99 @@
+use crate::{java, Object};
+
+const TO_ORIGINAL_BODY: &str = r#"
+// The declared type can be less specific than the enum, e.g. a field of type Object
+if (enumClass == null || !enumClass.isEnum()) return unwrap();
+
+var originals = ORIGINALS.computeIfAbsent(enumClass, clazz -> new Object[values().length]);
+if (originals[ordinal()] != null) return originals[ordinal()];
+
+// Name first, then aliases in the order they were declared
+for (var name : this.names) {
+    for (var constant : enumClass.getEnumConstants()) {
+        if (((Enum<?>) constant).name().equals(name)) {
+            originals[ordinal()] = constant;
+            return constant;
+        }
+    }
+}
+
+throw new RuntimeException("{{NAME}}Accessor." + name() + " has no matching constant in " + enumClass.getName() + ", tried: " + String.join(", ", this.names));
+"#;
+
+const UNWRAP_BODY: &str = r#"
+var enumClass = originalClass;
+{{TARGET_CLASS}}
+if (enumClass == null) throw new RuntimeException("{{NAME}}Accessor." + name() + " can't be unwrapped before any {{NAME}} was accessed, use toOriginal instead.");
+
+return toOriginal(enumClass);
+"#;
+
+// Bound enums can be loaded even if none was accessed yet
+const TARGET_CLASS_FALLBACK: &str = r#"
+if (enumClass == null) {
+    try {
+        enumClass = targetClass();
+    } catch (ClassNotFoundException e) {
+        throw new RuntimeException(e);
+    }
+}
+"#;
+
+pub fn generate(object: &Object) -> Vec<java::Method> {
+    vec![
+        java::Method::builder()
+            .name("toOriginal")
+            .return_type("Object")
+            .arguments(vec![java::Argument::builder()
+                .name("enumClass")
+                .type_name("Class<?>")
+                .build()])
+            .body(TO_ORIGINAL_BODY.replace("{{NAME}}", object.display_name()))
+            .build(),
+        java::Method::builder()
+            .name("unwrap")
+            .return_type("Object")
+            .body(
+                UNWRAP_BODY
+                    .replace("{{NAME}}", object.display_name())
+                    .replace(
+                        "{{TARGET_CLASS}}",
+                        if object.classes().next().is_some() {
+                            TARGET_CLASS_FALLBACK
+                        } else {
+                            ""
+                        },
+                    ),
+            )
+            .build(),
+    ]
+}
+
+/// Fields backing the conversion, the candidate names of each variant and the cached constants of
+/// every enum class it was converted into.
+pub fn fields() -> Vec<java::Field> {
+    vec![
+        java::Field::builder()
+            .name("names")
+            .type_name("String[]")
+            .visibility(java::Visibility::Private)
+            .is_final(true)
+            .build(),
+        java::Field::builder()
+            .name("ORIGINALS")
+            .type_name("java.util.Map<Class<?>, Object[]>")
+            .visibility(java::Visibility::Private)
+            .is_static(true)
+            .is_final(true)
+            .value("new java.util.concurrent.ConcurrentHashMap<>()")
+            .build(),
+        java::Field::builder()
+            .name("originalClass")
+            .type_name("Class<?>")
+            .comment("Enum class seen by the last call to access, used by unwrap")
+            .visibility(java::Visibility::Private)
+            .is_static(true)
+            .is_volatile(true)
+            .build(),
+    ]
+}
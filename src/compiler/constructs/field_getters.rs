@@ -0,0 +1,58 @@
+use crate::{java, Object};
+
+const BODY: &str = r#"
+{{PRESENCE_CHECK}}var field = {{FIELD_LOOKUP}};
+var value = {{READ}};
+return {{ACCESSOR}};
+"#;
+
+// Optional fields that don't exist read as their default value
+const PRESENCE_CHECK: &str = r#"if (!has_{{FIELD_NAME}}()) return {{DEFAULT}};
+
+"#;
+
+/// Live accessors don't copy the fields, instead every field gets a getter that reads the original
+/// object on every call. Nested accessors are created by the getter, when they are needed.
+pub fn generate(object: &Object) -> Vec<java::Method> {
+    if !object.is_live() {
+        return vec![];
+    }
+
+    object
+        .instance_fields()
+        .map(|field| {
+            let presence_check = if field.optional {
+                PRESENCE_CHECK
+                    .replace("{{FIELD_NAME}}", field.display_name())
+                    .replace("{{DEFAULT}}", field.field_type.default_value())
+            } else {
+                String::new()
+            };
+
+            java::Method::builder()
+                .name(format!("get_{}", field.display_name()))
+                .return_type(field.field_type.java_name(object))
+                .exceptions(
+                    vec![
+                        "NoSuchFieldException",
+                        "SecurityException",
+                        "IllegalArgumentException",
+                        "IllegalAccessException",
+                    ]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                )
+                .body(
+                    BODY.replace("{{PRESENCE_CHECK}}", &presence_check)
+                        .replace(
+                            "{{FIELD_LOOKUP}}",
+                            &field.lookup(&object.instance_class("this.self"), object.field_access),
+                        )
+                        .replace("{{READ}}", &field.read("this.self", object.field_access))
+                        .replace("{{ACCESSOR}}", &field.field_type.generate_accessor(object)),
+                )
+                .build()
+        })
+        .collect()
+}
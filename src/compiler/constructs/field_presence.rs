@@ -0,0 +1,74 @@
+use crate::{java, Object};
+
+const INSTANCE_BODY: &str = r#"
+return this.present_{{FIELD_NAME}};
+"#;
+
+const LIVE_BODY: &str = r#"
+try {
+    {{FIELD_LOOKUP}};
+    return true;
+} catch (NoSuchFieldException e) {
+    return false;
+}
+"#;
+
+const STATIC_BODY: &str = r#"
+try {
+    {{FIELD_LOOKUP}};
+    return true;
+} catch (ClassNotFoundException | NoSuchFieldException e) {
+    return false;
+}
+"#;
+
+/// Presence checks of the optional fields, `has_<field>()`. Instance fields of snapshots remember
+/// if they were found when accessed, other fields are looked up on every call.
+pub fn generate(object: &Object) -> Vec<java::Method> {
+    object
+        .fields
+        .iter()
+        .filter(|field| field.optional)
+        .map(|field| {
+            let body = if field.is_static {
+                STATIC_BODY.replace(
+                    "{{FIELD_LOOKUP}}",
+                    &field.lookup("targetClass()", object.field_access),
+                )
+            } else if object.is_live() {
+                LIVE_BODY.replace(
+                    "{{FIELD_LOOKUP}}",
+                    &field.lookup(&object.instance_class("this.self"), object.field_access),
+                )
+            } else {
+                INSTANCE_BODY.replace("{{FIELD_NAME}}", field.display_name())
+            };
+
+            java::Method::builder()
+                .is_static(field.is_static)
+                .name(format!("has_{}", field.display_name()))
+                .return_type("boolean")
+                .body(body)
+                .build()
+        })
+        .collect()
+}
+
+/// Flags backing the presence checks of the optional instance fields of snapshots.
+pub fn fields(object: &Object) -> Vec<java::Field> {
+    if object.is_live() {
+        return vec![];
+    }
+
+    object
+        .instance_fields()
+        .filter(|field| field.optional)
+        .map(|field| {
+            java::Field::builder()
+                .name(format!("present_{}", field.display_name()))
+                .type_name("boolean")
+                .visibility(java::Visibility::Private)
+                .build()
+        })
+        .collect()
+}
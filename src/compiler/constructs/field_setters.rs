@@ -1,22 +1,33 @@
 use crate::{java, Object};
 
 const BODY: &str = r#"
-try {
-    var clazz = this.self.getClass();
-    var field = clazz.{{GET_METHOD}}("{{TRUE_FIELD_NAME}}");
-    field.setAccessible(true);
-    field.set(this.self, value);
-
-    this.{{FIELD_NAME}} = value;
-} catch (Exception e) {
+{{PRESENCE_CHECK}}try {
+    var field = {{FIELD_LOOKUP}};
+    {{WRITE}}
+{{UPDATE_COPY}}} catch (Exception e) {
     System.out.println("Error setting field '{{FIELD_NAME}}':");
     e.printStackTrace();
 }
 "#;
 
-pub fn generate(fields: &[crate::Field], object: &Object) -> Vec<java::Method> {
+// Optional fields that weren't found have nothing to write to
+const PRESENCE_CHECK: &str = r#"if (!has_{{FIELD_NAME}}()) return;
+
+"#;
+
+// Snapshots keep their copy of the field in sync, live accessors have none
+const UPDATE_COPY: &str = r#"
+    this.{{FIELD_NAME}} = value;
+"#;
+
+pub fn generate(
+    fields: &[crate::Field],
+    object: &Object,
+    includes: &[Object],
+) -> Vec<java::Method> {
     fields
         .iter()
+        .filter(|field| !field.is_static)
         .map(|field| {
             java::Method::builder()
                 .name(format!("set_{}", field.display_name()))
@@ -26,14 +37,30 @@ pub fn generate(fields: &[crate::Field], object: &Object) -> Vec<java::Method> {
                     .build()])
                 .body(
                     BODY.replace(
-                        "{{GET_METHOD}}",
-                        if field.hierarchy {
-                            "getField"
-                        } else {
-                            "getDeclaredField"
-                        },
+                        "{{FIELD_LOOKUP}}",
+                        &field.lookup(&object.instance_class("this.self"), object.field_access),
+                    )
+                    .replace(
+                        "{{WRITE}}",
+                        &field.write(
+                            "this.self",
+                            &field.field_type.unwrap_expression(
+                                "value",
+                                Some(object.field_access.value_class()),
+                                object,
+                                includes,
+                            ),
+                            object.field_access,
+                        ),
+                    )
+                    .replace(
+                        "{{PRESENCE_CHECK}}",
+                        if field.optional { PRESENCE_CHECK } else { "" },
+                    )
+                    .replace(
+                        "{{UPDATE_COPY}}",
+                        if object.is_live() { "" } else { UPDATE_COPY },
                     )
-                    .replace("{{TRUE_FIELD_NAME}}", &field.name)
                     .replace("{{FIELD_NAME}}", field.display_name()),
                 )
                 .build()
@@ -0,0 +1,127 @@
+use crate::{java, FieldAccess};
+
+const BODY: &str = r#"
+// Fields are resolved once per runtime class, then reused by every access
+var fields = FIELDS.computeIfAbsent(clazz, x -> new java.util.concurrent.ConcurrentHashMap<>());
+// The same name can be looked up up to different ancestors
+var key = hierarchy ? names[0] + "^" + stopAt : names[0];
+var cached = fields.get(key);
+if (cached != null) return cached;
+
+// Every candidate is tried in a class before its superclass, the closest declaration wins
+for (var current = clazz; current != null; current = hierarchy ? current.getSuperclass() : null) {
+    for (var name : names) {
+        try {
+            var field = current.getDeclaredField(name);
+            field.setAccessible(true);
+            fields.put(key, field);
+            return field;
+        } catch (NoSuchFieldException e) {
+            // Try the next candidate
+        }
+    }
+
+    if (current.getName().equals(stopAt)) break;
+}
+
+throw new NoSuchFieldException(clazz.getName() + " has none of the fields: " + String.join(", ", names));
+"#;
+
+const VAR_HANDLE_BODY: &str = r#"
+var handles = VAR_HANDLES.computeIfAbsent(clazz, x -> new java.util.concurrent.ConcurrentHashMap<>());
+var key = hierarchy ? names[0] + "^" + stopAt : names[0];
+var cached = handles.get(key);
+if (cached != null) return cached;
+
+var field = findField(clazz, names, hierarchy, stopAt);
+try {
+    var lookup = java.lang.invoke.MethodHandles.privateLookupIn(field.getDeclaringClass(), java.lang.invoke.MethodHandles.lookup());
+    var handle = lookup.unreflectVarHandle(field);
+    handles.put(key, handle);
+    return handle;
+} catch (IllegalAccessException e) {
+    // Same as the field not being accessible through reflection
+    throw new RuntimeException("Could not access field " + field.getName() + " of " + clazz.getName(), e);
+}
+"#;
+
+fn arguments() -> Vec<java::Argument> {
+    vec![
+        java::Argument::builder()
+            .name("clazz")
+            .type_name("Class<?>")
+            .build(),
+        java::Argument::builder()
+            .name("names")
+            .type_name("String[]")
+            .build(),
+        java::Argument::builder()
+            .name("hierarchy")
+            .type_name("boolean")
+            .build(),
+        java::Argument::builder()
+            .name("stopAt")
+            .type_name("String")
+            .build(),
+    ]
+}
+
+/// Field lookups, `findField` always, since var handles are made from the reflected field, and
+/// `findVarHandle` when fields are accessed through handles.
+pub fn generate(access: FieldAccess) -> Vec<java::Method> {
+    let find_field = java::Method::builder()
+        .name("findField")
+        .is_static(true)
+        .visibility(java::Visibility::Private)
+        .exceptions(vec!["NoSuchFieldException".to_string()])
+        .arguments(arguments())
+        .return_type("java.lang.reflect.Field")
+        .body(BODY)
+        .build();
+
+    match access {
+        FieldAccess::Reflection => vec![find_field],
+        FieldAccess::Handles => vec![
+            find_field,
+            java::Method::builder()
+                .name("findVarHandle")
+                .is_static(true)
+                .visibility(java::Visibility::Private)
+                .exceptions(vec!["NoSuchFieldException".to_string()])
+                .arguments(arguments())
+                .return_type("java.lang.invoke.VarHandle")
+                .body(VAR_HANDLE_BODY)
+                .build(),
+        ],
+    }
+}
+
+/// Caches of the resolved fields, and their var handles, keyed by runtime class, the first
+/// candidate name and the ancestor the lookup stops at.
+pub fn fields(access: FieldAccess) -> Vec<java::Field> {
+    let mut fields = vec![java::Field::builder()
+        .name("FIELDS")
+        .type_name("java.util.Map<Class<?>, java.util.Map<String, java.lang.reflect.Field>>")
+        .visibility(java::Visibility::Private)
+        .is_static(true)
+        .is_final(true)
+        .value("new java.util.concurrent.ConcurrentHashMap<>()")
+        .build()];
+
+    if access == FieldAccess::Handles {
+        fields.push(
+            java::Field::builder()
+                .name("VAR_HANDLES")
+                .type_name(
+                    "java.util.Map<Class<?>, java.util.Map<String, java.lang.invoke.VarHandle>>",
+                )
+                .visibility(java::Visibility::Private)
+                .is_static(true)
+                .is_final(true)
+                .value("new java.util.concurrent.ConcurrentHashMap<>()")
+                .build(),
+        );
+    }
+
+    fields
+}
@@ -0,0 +1,75 @@
+use crate::java;
+
+const BODY: &str = r#"
+// Methods are resolved once per runtime class, then reused by every call
+var methods = METHODS.computeIfAbsent(clazz, x -> new java.util.concurrent.ConcurrentHashMap<>());
+var key = name + java.util.Arrays.toString(parameters);
+var cached = methods.get(key);
+if (cached != null) return cached;
+
+for (var current = clazz; current != null; current = hierarchy ? current.getSuperclass() : null) {
+    for (var method : current.getDeclaredMethods()) {
+        if (!method.getName().equals(name) || method.getParameterCount() != parameters.length) continue;
+
+        var types = method.getParameterTypes();
+        var matches = true;
+
+        // Parameters whose original type is unknown (accessors) are null, and match any non primitive
+        for (var i = 0; i < parameters.length; i++) {
+            if (parameters[i] == null ? types[i].isPrimitive() : parameters[i] != types[i]) {
+                matches = false;
+                break;
+            }
+        }
+
+        if (matches) {
+            method.setAccessible(true);
+            methods.put(key, method);
+            return method;
+        }
+    }
+}
+
+throw new NoSuchMethodException(clazz.getName() + "." + name + " with " + parameters.length + " parameters");
+"#;
+
+pub fn generate() -> java::Method {
+    java::Method::builder()
+        .name("findMethod")
+        .is_static(true)
+        .visibility(java::Visibility::Private)
+        .exceptions(vec!["NoSuchMethodException".to_string()])
+        .arguments(vec![
+            java::Argument::builder()
+                .name("clazz")
+                .type_name("Class<?>")
+                .build(),
+            java::Argument::builder()
+                .name("name")
+                .type_name("String")
+                .build(),
+            java::Argument::builder()
+                .name("parameters")
+                .type_name("Class<?>[]")
+                .build(),
+            java::Argument::builder()
+                .name("hierarchy")
+                .type_name("boolean")
+                .build(),
+        ])
+        .return_type("java.lang.reflect.Method")
+        .body(BODY)
+        .build()
+}
+
+/// Cache of the resolved methods, keyed by runtime class, name and parameter types.
+pub fn fields() -> Vec<java::Field> {
+    vec![java::Field::builder()
+        .name("METHODS")
+        .type_name("java.util.Map<Class<?>, java.util.Map<String, java.lang.reflect.Method>>")
+        .visibility(java::Visibility::Private)
+        .is_static(true)
+        .is_final(true)
+        .value("new java.util.concurrent.ConcurrentHashMap<>()")
+        .build()]
+}
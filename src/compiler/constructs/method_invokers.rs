@@ -0,0 +1,107 @@
+use crate::{java, Object};
+
+const BODY: &str = r#"
+var method = findMethod({{CLASS}}, "{{TRUE_METHOD_NAME}}", new Class<?>[] {{{PARAMETER_TYPES}}}, {{HIERARCHY}});
+var value = method.invoke({{RECEIVER}}{{ARGUMENTS}});
+"#;
+
+const RETURN_BODY: &str = r#"
+return {{ACCESSOR}};
+"#;
+
+pub fn generate(object: &Object, includes: &[Object]) -> Vec<java::Method> {
+    object
+        .methods
+        .iter()
+        .map(|method| {
+            let arguments = method
+                .parameters
+                .iter()
+                .enumerate()
+                .map(|(i, parameter)| {
+                    java::Argument::builder()
+                        .name(format!("arg{i}"))
+                        .type_name(parameter.java_name(object))
+                        .build()
+                })
+                .collect::<Vec<java::Argument>>();
+
+            let parameter_types = method
+                .parameters
+                .iter()
+                .map(|x| x.java_class().unwrap_or("null".to_string()))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            // Accessors are unwrapped into the original values, the real parameter types are used
+            // to resolve enums and create arrays
+            let invoke_arguments = method
+                .parameters
+                .iter()
+                .enumerate()
+                .map(|(i, parameter)| {
+                    format!(
+                        ", {}",
+                        parameter.unwrap_expression(
+                            &format!("arg{i}"),
+                            Some(&format!("method.getParameterTypes()[{i}]")),
+                            object,
+                            includes,
+                        )
+                    )
+                })
+                .collect::<String>();
+
+            // Static methods are invoked on the original class, without a receiver
+            let (class, receiver) = if method.is_static {
+                ("targetClass()".to_string(), "null")
+            } else {
+                (object.instance_class("this.self"), "this.self")
+            };
+
+            let mut body = BODY
+                .replace("{{CLASS}}", &class)
+                .replace("{{RECEIVER}}", receiver)
+                .replace("{{TRUE_METHOD_NAME}}", &method.name)
+                .replace(
+                    "{{PARAMETER_TYPES}}",
+                    &if parameter_types.is_empty() {
+                        parameter_types
+                    } else {
+                        format!(" {parameter_types} ")
+                    },
+                )
+                .replace("{{HIERARCHY}}", &method.hierarchy.to_string())
+                .replace("{{ARGUMENTS}}", &invoke_arguments);
+
+            if let Some(return_type) = &method.return_type {
+                body.push_str(&RETURN_BODY.replace(
+                    "{{ACCESSOR}}",
+                    &return_type.access_expression("value", object),
+                ));
+            }
+
+            java::Method::builder()
+                .is_static(method.is_static)
+                .name(method.display_name())
+                .arguments(arguments)
+                .maybe_return_type(method.return_type.as_ref().map(|x| x.java_name(object)))
+                .exceptions(
+                    vec![
+                        "ClassNotFoundException",
+                        "NoSuchMethodException",
+                        "NoSuchFieldException",
+                        "SecurityException",
+                        "IllegalArgumentException",
+                        "IllegalAccessException",
+                        "java.lang.reflect.InvocationTargetException",
+                    ]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                )
+                .body(body)
+                .build()
+        })
+        .collect()
+}
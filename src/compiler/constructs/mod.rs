@@ -1,8 +1,19 @@
 pub mod array_accessor;
 pub mod class_constructor;
+pub mod class_unwrap;
 pub mod clear_inner_refs;
 pub mod clear_inner_refs_array;
+pub mod collection_accessor;
+pub mod collection_unwrapper;
+pub mod enum_constructor;
+pub mod enum_to_original;
+pub mod field_getters;
+pub mod field_presence;
 pub mod field_setters;
-pub mod nested_array_accessor;
+pub mod find_field;
+pub mod find_method;
+pub mod method_invokers;
 pub mod single_class_accessor;
 pub mod single_enum_accessor;
+pub mod static_field_accessors;
+pub mod target_class;
@@ -1,28 +1,61 @@
-use crate::{java, Object, Type};
+use crate::{java, Object};
 
-const START_BODY: &str = r#"
+const ACCESS_BODY: &str = r#"
 if (object == null) return null;
 
 var accessor = new {{CLASS_NAME}}Accessor(object);
+accessFields(object, accessor);
+return accessor;
+"#;
+
+const START_BODY: &str = r#"
 var clazz = object.getClass();
 if (clazz.isEnum()) throw new RuntimeException("Failed to access {{CLASS_NAME}}: Expected object to be object but got enum.");
-"#;
+{{MATCH_CLASS}}{{PARENT}}"#;
 
 const FIELD_BODY: &str = r#"
 {
-    var field = clazz.{{FIELD_GET_METHOD}}("{{TRUE_FIELD_NAME}}");
-    field.setAccessible(true);
-    var value = field.{{GET_METHOD}}(object);
+    var field = {{FIELD_LOOKUP}};
+    var value = {{READ}};
     accessor.{{FIELD_NAME}} = {{ACCESSOR}};
 }
 "#;
 
-const END_BODY: &str = r#"
-return accessor;
+// Missing optional fields are skipped, leaving their default value
+const OPTIONAL_FIELD_BODY: &str = r#"
+{
+    {{HANDLE_TYPE}} field;
+    try {
+        field = {{FIELD_LOOKUP}};
+    } catch (NoSuchFieldException e) {
+        field = null;
+    }
+
+    if (field != null) {
+        var value = {{READ}};
+        accessor.{{FIELD_NAME}} = {{ACCESSOR}};
+        accessor.present_{{FIELD_NAME}} = true;
+    }
+}
 "#;
 
-pub fn generate(object: &Object) -> java::Method {
-    java::Method::builder()
+fn exceptions() -> Vec<String> {
+    vec![
+        "NoSuchFieldException",
+        "SecurityException",
+        "IllegalArgumentException",
+        "IllegalAccessException",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+/// `access`, and `accessFields` which fills the fields of an accessor that was already created.
+/// Accessors extending this one fill the inherited fields through it, looking them up in the
+/// original class of this object.
+pub fn generate(object: &Object) -> Vec<java::Method> {
+    let access = java::Method::builder()
         .is_static(true)
         .name("access")
         .return_type(format!("{}Accessor", object.display_name()))
@@ -30,65 +63,74 @@ pub fn generate(object: &Object) -> java::Method {
             .name("object")
             .type_name("Object")
             .build()])
-        .exceptions(
-            vec![
-                "NoSuchFieldException",
-                "SecurityException",
-                "IllegalArgumentException",
-                "IllegalAccessException",
-            ]
-            .into_iter()
-            .map(String::from)
-            .collect(),
-        )
+        .exceptions(exceptions())
+        .body(ACCESS_BODY.replace("{{CLASS_NAME}}", object.display_name()))
+        .build();
+
+    let access_fields = java::Method::builder()
+        .is_static(true)
+        .visibility(java::Visibility::Protected)
+        .name("accessFields")
+        .arguments(vec![
+            java::Argument::builder()
+                .name("object")
+                .type_name("Object")
+                .build(),
+            java::Argument::builder()
+                .name("accessor")
+                .type_name(format!("{}Accessor", object.display_name()))
+                .build(),
+        ])
+        .exceptions(exceptions())
         .body(
             vec![START_BODY
                 .replace("{{CLASS_NAME}}", object.display_name())
+                .replace(
+                    "{{MATCH_CLASS}}",
+                    &if object.classes().next().is_some() {
+                        format!("clazz = {};\n", object.instance_class("object"))
+                    } else {
+                        String::new()
+                    },
+                )
+                .replace(
+                    "{{PARENT}}",
+                    &match &object.extends {
+                        Some(parent) => {
+                            format!("{parent}Accessor.accessFields(object, accessor);\n")
+                        }
+                        None => String::new(),
+                    },
+                )
                 .to_owned()]
             .into_iter()
+            // Live accessors read the fields through their getters instead
             .chain(
                 object
-                    .fields
-                    .iter()
+                    .instance_fields()
+                    .filter(|_| !object.is_live())
                     .map(|field| {
-                        FIELD_BODY
-                            .replace(
-                                "{{FIELD_GET_METHOD}}",
-                                if field.hierarchy {
-                                    "getField"
-                                } else {
-                                    "getDeclaredField"
-                                },
-                            )
-                            .replace("{{TRUE_FIELD_NAME}}", &field.name)
-                            .replace(
-                                "{{GET_METHOD}}",
-                                match &field.field_type {
-                                    Type::Bool => "getBoolean",
-                                    Type::I32 => "getInt",
-                                    Type::I64 => "getLong",
-                                    Type::U8 => "getByte",
-                                    Type::I16 => "getShort",
-                                    Type::F32 => "getFloat",
-                                    Type::F64 => "getDouble",
-                                    Type::String
-                                    | Type::Array(_)
-                                    | Type::Object(_)
-                                    | Type::SelfObject => "get",
-                                },
-                            )
-                            .replace("{{FIELD_NAME}}", field.display_name())
-                            .replace(
-                                "{{ACCESSOR}}",
-                                &field.field_type.generate_accessor(true, object),
-                            )
-                            .to_string()
+                        if field.optional {
+                            OPTIONAL_FIELD_BODY
+                        } else {
+                            FIELD_BODY
+                        }
+                        .replace(
+                            "{{FIELD_LOOKUP}}",
+                            &field.lookup("clazz", object.field_access),
+                        )
+                        .replace("{{HANDLE_TYPE}}", object.field_access.handle_type())
+                        .replace("{{READ}}", &field.read("object", object.field_access))
+                        .replace("{{FIELD_NAME}}", field.display_name())
+                        .replace("{{ACCESSOR}}", &field.field_type.generate_accessor(object))
+                        .to_string()
                     })
                     .collect::<Vec<String>>(),
             )
-            .chain(vec![END_BODY.to_string()])
             .collect::<Vec<String>>()
             .join("\n"),
         )
-        .build()
+        .build();
+
+    vec![access, access_fields]
 }
@@ -7,6 +7,8 @@ var clazz = object.getClass();
 
 if (!clazz.isEnum()) throw new RuntimeException("{{NAME}} was supposed to be an enum but it is not!");
 
+originalClass = {{ENUM_CLASS}};
+
 var variant = ((Enum<?>) object).name();
 
 switch (variant) {
@@ -44,6 +46,14 @@ pub fn generate(object: &Object) -> java::Method {
         .body(
             vec![START_BODY
                 .replace("{{NAME}}", object.display_name())
+                .replace(
+                    "{{ENUM_CLASS}}",
+                    if object.classes().next().is_some() {
+                        "matchTargetClass(((Enum<?>) object).getDeclaringClass())"
+                    } else {
+                        "((Enum<?>) object).getDeclaringClass()"
+                    },
+                )
                 .to_owned()]
             .into_iter()
             .chain(
@@ -51,9 +61,16 @@ pub fn generate(object: &Object) -> java::Method {
                     .variants
                     .iter()
                     .map(|variant| {
+                        // Aliases share the case of the variant, so renamed constants from other
+                        // versions resolve to the same accessor
+                        let labels = std::iter::once(&variant.name)
+                            .chain(&variant.aliases)
+                            .map(|name| format!("case \"{name}\":"))
+                            .collect::<Vec<String>>()
+                            .join(" ");
+
                         format!(
-                            "\tcase \"{}\": return {}Accessor.{};",
-                            variant.name,
+                            "\t{labels} return {}Accessor.{};",
                             object.display_name(),
                             variant.display_name()
                         )
@@ -0,0 +1,107 @@
+use crate::{java, Object};
+
+const GET_BODY: &str = r#"
+{{PRESENCE_CHECK}}var field = {{FIELD_LOOKUP}};
+var value = {{READ}};
+return {{ACCESSOR}};
+"#;
+
+const SET_BODY: &str = r#"
+{{PRESENCE_CHECK}}try {
+    var field = {{FIELD_LOOKUP}};
+    {{WRITE}}
+} catch (Exception e) {
+    System.out.println("Error setting static field '{{FIELD_NAME}}':");
+    e.printStackTrace();
+}
+"#;
+
+// Optional fields that don't exist read as their default value and ignore writes
+const PRESENCE_CHECK: &str = r#"if (!has_{{FIELD_NAME}}()) return{{DEFAULT}};
+
+"#;
+
+/// Static fields aren't part of the accessor instance, instead they get a static getter and setter
+/// that read and write the field of the original class.
+pub fn generate(object: &Object, includes: &[Object]) -> Vec<java::Method> {
+    object
+        .static_fields()
+        .flat_map(|field| {
+            [
+                java::Method::builder()
+                    .is_static(true)
+                    .name(format!("get_{}", field.display_name()))
+                    .return_type(field.field_type.java_name(object))
+                    .exceptions(
+                        vec![
+                            "ClassNotFoundException",
+                            "NoSuchFieldException",
+                            "SecurityException",
+                            "IllegalArgumentException",
+                            "IllegalAccessException",
+                        ]
+                        .into_iter()
+                        .map(String::from)
+                        .collect(),
+                    )
+                    .body(
+                        GET_BODY
+                            .replace(
+                                "{{PRESENCE_CHECK}}",
+                                &presence_check(
+                                    field,
+                                    &format!(" {}", field.field_type.default_value()),
+                                ),
+                            )
+                            .replace(
+                                "{{FIELD_LOOKUP}}",
+                                &field.lookup("targetClass()", object.field_access),
+                            )
+                            .replace("{{READ}}", &field.read("null", object.field_access))
+                            .replace("{{ACCESSOR}}", &field.field_type.generate_accessor(object)),
+                    )
+                    .build(),
+                java::Method::builder()
+                    .is_static(true)
+                    .name(format!("set_{}", field.display_name()))
+                    .arguments(vec![java::Argument::builder()
+                        .name("value")
+                        .type_name(field.field_type.java_name(object))
+                        .build()])
+                    .body(
+                        SET_BODY
+                            .replace("{{PRESENCE_CHECK}}", &presence_check(field, ""))
+                            .replace(
+                                "{{FIELD_LOOKUP}}",
+                                &field.lookup("targetClass()", object.field_access),
+                            )
+                            .replace("{{FIELD_NAME}}", field.display_name())
+                            .replace(
+                                "{{WRITE}}",
+                                &field.write(
+                                    "null",
+                                    &field.field_type.unwrap_expression(
+                                        "value",
+                                        Some(object.field_access.value_class()),
+                                        object,
+                                        includes,
+                                    ),
+                                    object.field_access,
+                                ),
+                            ),
+                    )
+                    .build(),
+            ]
+        })
+        .collect()
+}
+
+fn presence_check(field: &crate::Field, returned: &str) -> String {
+    if field.optional {
+        PRESENCE_CHECK
+            .replace("{{FIELD_NAME}}", field.display_name())
+            .replace("{{DEFAULT}}", returned)
+    } else {
+        String::new()
+    }
+}
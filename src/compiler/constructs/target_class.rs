@@ -0,0 +1,85 @@
+use crate::{java, Object};
+
+const TARGET_CLASS_BODY: &str = r#"
+for (var name : new String[] { {{CLASSES}} }) {
+    try {
+        return Class.forName(name);
+    } catch (ClassNotFoundException e) {
+        // Try the next alternate
+    }
+}
+
+throw new ClassNotFoundException("{{NAME}}Accessor could not find any of its original classes: {{CLASS_NAMES}}");
+"#;
+
+const MATCH_TARGET_CLASS_BODY: &str = r#"
+for (var current = clazz; current != null; current = current.getSuperclass()) {
+    switch (current.getName()) {
+        {{CASES}} return current;
+    }
+}
+
+throw new IllegalArgumentException("{{NAME}}Accessor expected an instance of {{CLASS_NAMES}} but got " + clazz.getName());
+"#;
+
+/// Methods resolving the original class the object is bound to. `targetClass` loads it to reach
+/// static members and to create arrays of it from other accessors, `matchTargetClass` checks an
+/// instance against it.
+pub fn generate(object: &Object) -> Vec<java::Method> {
+    let classes = object.classes().collect::<Vec<&String>>();
+    if classes.is_empty() {
+        return vec![];
+    }
+
+    let class_names = classes
+        .iter()
+        .map(|x| x.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ");
+
+    vec![
+        java::Method::builder()
+            .name("targetClass")
+            .is_static(true)
+            .visibility(java::Visibility::Public)
+            .exceptions(vec!["ClassNotFoundException".to_string()])
+            .return_type("Class<?>")
+            .body(
+                TARGET_CLASS_BODY
+                    .replace(
+                        "{{CLASSES}}",
+                        &classes
+                            .iter()
+                            .map(|x| format!("\"{x}\""))
+                            .collect::<Vec<String>>()
+                            .join(", "),
+                    )
+                    .replace("{{NAME}}", object.display_name())
+                    .replace("{{CLASS_NAMES}}", &class_names),
+            )
+            .build(),
+        java::Method::builder()
+            .name("matchTargetClass")
+            .is_static(true)
+            .visibility(java::Visibility::Private)
+            .arguments(vec![java::Argument::builder()
+                .name("clazz")
+                .type_name("Class<?>")
+                .build()])
+            .return_type("Class<?>")
+            .body(
+                MATCH_TARGET_CLASS_BODY
+                    .replace(
+                        "{{CASES}}",
+                        &classes
+                            .iter()
+                            .map(|x| format!("case \"{x}\":"))
+                            .collect::<Vec<String>>()
+                            .join(" "),
+                    )
+                    .replace("{{NAME}}", object.display_name())
+                    .replace("{{CLASS_NAMES}}", &class_names),
+            )
+            .build(),
+    ]
+}
@@ -0,0 +1,564 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::{Field, Object, Type};
+
+use super::{JavaSource, SourceError};
+
+/// Module file declaring the generated modules of a directory
+pub const MODULE_FILE: &str = "mod.rs";
+
+const HEADER: &str = r#"#![allow(dead_code, unused_imports, unused_unsafe, clippy::all)]
+{{COMMENT}}
+
+use std::sync::OnceLock;
+
+use jni::errors::{Error, Result};
+use jni::objects::{GlobalRef, JClass, JFieldID, JObject, JStaticFieldID, JString, JValue};
+use jni::signature::{JavaType, Primitive, ReturnType};
+use jni::JNIEnv;
+"#;
+
+const STRUCT: &str = r#"
+/// Wraps an instance of `{{CLASS}}`, every call reads or writes the original object.
+pub struct {{NAME}}Accessor<'local> {
+    object: JObject<'local>,
+}
+
+impl<'local> {{NAME}}Accessor<'local> {
+    /// Fails when `object` is null{{NEW_DOC}}
+    pub fn new(env: &mut JNIEnv<'local>, object: JObject<'local>) -> Result<Self> {
+        if object.is_null() {
+            return Err(Error::NullPtr("object"));
+        }
+{{INSTANCE_CHECK}}
+        Ok(Self { object })
+    }
+
+    pub fn as_obj(&self) -> &JObject<'local> {
+        &self.object
+    }
+
+    pub fn unwrap(self) -> JObject<'local> {
+        self.object
+    }
+{{MEMBERS}}}
+"#;
+
+const GETTER: &str = r#"
+{{COMMENT}}    pub fn get_{{FIELD_NAME}}({{RECEIVER}}env: &mut JNIEnv<'local>) -> Result<{{RUST_TYPE}}> {
+{{PRESENCE_CHECK}}        {{CLASS}}
+        let id = {{LOOKUP}}?;
+        // SAFETY: the field was looked up with the signature of the value
+        unsafe { env.{{GET}}({{TARGET}}, id, {{VALUE_TYPE}}) }?.{{UNPACK}}
+    }
+"#;
+
+const SETTER: &str = r#"
+{{COMMENT}}    pub fn set_{{FIELD_NAME}}({{RECEIVER}}env: &mut JNIEnv<'local>, value: {{ARGUMENT_TYPE}}) -> Result<()> {
+{{PRESENCE_CHECK}}{{VALUE_CHECK}}        {{CLASS}}
+        let id = {{LOOKUP}}?;
+        // SAFETY: the field was looked up with the signature of the value
+        unsafe { env.{{SET}}({{TARGET}}, id, {{VALUE}}) }
+    }
+"#;
+
+// Only the field IDs of the original class are cached, the instance has to be one of it
+const INSTANCE_CHECK: &str = r#"
+        let class = target_class(env)?;
+        if !env.is_instance_of(&object, class)? {
+            return Err(Error::WrongJValueType("{{CLASS}}", "an object of another class"));
+        }
+"#;
+
+// Objects are written without checks, so they are checked against the field type first
+const VALUE_CHECK: &str = r#"        if !value.is_null() && !env.is_instance_of(value, "{{VALUE_CLASS}}")? {
+            return Err(Error::WrongJValueType("{{SIGNATURE}}", "an object of another class"));
+        }
+
+"#;
+
+const PRESENCE: &str = r#"
+    pub fn has_{{FIELD_NAME}}({{RECEIVER}}env: &mut JNIEnv<'local>) -> Result<bool> {
+        {{CLASS}}
+        match {{LOOKUP}} {
+            Ok(_) => Ok(true),
+            Err(Error::FieldNotFound { .. }) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+"#;
+
+const FIELD_ID: &str = r#"
+/// Field ID looked up by the first of `names` the class has, cached in `cache` when the class is
+/// always the same
+fn field_id(
+    env: &mut JNIEnv,
+    class: &JClass,
+    cache: Option<&OnceLock<JFieldID>>,
+    names: &[&str],
+    signature: &str,
+) -> Result<JFieldID> {
+    if let Some(id) = cache.and_then(OnceLock::get) {
+        return Ok(*id);
+    }
+
+    for name in names {
+        match env.get_field_id(class, *name, signature) {
+            Ok(id) => return Ok(cache.map_or(id, |x| *x.get_or_init(|| id))),
+            // The failed lookup leaves a NoSuchFieldError pending
+            Err(Error::JavaException | Error::FieldNotFound { .. }) => env.exception_clear()?,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Err(Error::FieldNotFound {
+        name: names.join(", "),
+        sig: signature.to_string(),
+    })
+}
+"#;
+
+const STATIC_FIELD_ID: &str = r#"
+/// Static field ID cached in `cache`, looked up by the first of `names` the class has
+fn static_field_id(
+    env: &mut JNIEnv,
+    class: &JClass,
+    cache: &OnceLock<JStaticFieldID>,
+    names: &[&str],
+    signature: &str,
+) -> Result<JStaticFieldID> {
+    if let Some(id) = cache.get() {
+        return Ok(*id);
+    }
+
+    for name in names {
+        match env.get_static_field_id(class, *name, signature) {
+            Ok(id) => return Ok(*cache.get_or_init(|| id)),
+            // The failed lookup leaves a NoSuchFieldError pending
+            Err(Error::JavaException | Error::FieldNotFound { .. }) => env.exception_clear()?,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Err(Error::FieldNotFound {
+        name: names.join(", "),
+        sig: signature.to_string(),
+    })
+}
+"#;
+
+const TARGET_CLASS: &str = r#"
+static CLASS: OnceLock<GlobalRef> = OnceLock::new();
+
+/// The first of the original class and its alternates that can be loaded, kept alive by a global
+/// reference so the cached field IDs stay valid.
+fn target_class(env: &mut JNIEnv) -> Result<&'static JClass<'static>> {
+    if let Some(class) = CLASS.get() {
+        return Ok(<&JClass>::from(class.as_obj()));
+    }
+
+    for name in [{{CLASSES}}] {
+        match env.find_class(name) {
+            Ok(class) => {
+                let class = env.new_global_ref(class)?;
+                return Ok(<&JClass>::from(CLASS.get_or_init(|| class).as_obj()));
+            }
+            // The failed lookup leaves a NoClassDefFoundError pending
+            Err(Error::JavaException) => env.exception_clear()?,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Err(Error::JavaException)
+}
+"#;
+
+/// Rust module with a struct wrapping the original object, with a getter and setter for every
+/// field. Fields are reached through JNI field IDs, looked up once and cached.
+/// JNI checks signatures, so every object used by a field has to declare it's class.
+/// The wrapped objects and the objects written to fields are checked against their classes,
+/// fields of objects without a class are looked up in the class of every instance.
+/// The fields of the extended objects are accessed like the object's own, JNI finds them in the
+/// superclasses.
+/// * `inherited` - Objects extended by `object`, each with the objects it references
+pub fn generate(
+    object: &Object,
+    includes: &[Object],
+    inherited: &[(Object, Vec<Object>)],
+    comment: String,
+) -> Result<String, SourceError> {
+    let bound = object.classes().next().is_some();
+
+    let mut caches = vec![];
+    let mut items = vec![];
+    let mut members = vec![];
+
+    // Types of the fields are resolved from the object declaring them
+    let fields = object
+        .fields
+        .iter()
+        .map(|x| (x, object, includes))
+        .chain(inherited.iter().flat_map(|(ancestor, includes)| {
+            ancestor
+                .fields
+                .iter()
+                .map(move |x| (x, ancestor, includes.as_slice()))
+        }))
+        .collect::<Vec<(&Field, &Object, &[Object])>>();
+
+    for (field, owner, includes) in fields.iter().copied() {
+        let signature = field
+            .field_type
+            .jni_signature(owner, includes)
+            .map_err(SourceError::MissingJniClass)?;
+        let cache = format!(
+            "{}FIELD_{}",
+            if field.is_static { "STATIC_" } else { "" },
+            snake_case(field.display_name()).to_uppercase()
+        );
+
+        // Instances of objects without a class can be of any class
+        if field.is_static || bound {
+            caches.push(format!(
+                "static {cache}: OnceLock<{}> = OnceLock::new();",
+                if field.is_static {
+                    "JStaticFieldID"
+                } else {
+                    "JFieldID"
+                }
+            ));
+        }
+
+        let accessor = Accessor::new(field, &cache, &signature, bound);
+        members.push(accessor.getter());
+        members.push(accessor.setter());
+        if field.optional {
+            members.push(accessor.presence());
+        }
+    }
+
+    if !caches.is_empty() {
+        items.push(caches.join("\n"));
+    }
+    if fields.iter().any(|(x, _, _)| !x.is_static) {
+        items.push(FIELD_ID.trim().to_string());
+    }
+    if fields.iter().any(|(x, _, _)| x.is_static) {
+        items.push(STATIC_FIELD_ID.trim().to_string());
+    }
+
+    if bound {
+        let classes = object
+            .classes()
+            .map(|x| format!("\"{}\"", x.replace('.', "/")))
+            .collect::<Vec<String>>()
+            .join(", ");
+        items.push(TARGET_CLASS.trim().replace("{{CLASSES}}", &classes));
+    }
+
+    let comment = comment
+        .lines()
+        .map(|x| format!("// {x}"))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    Ok(
+        std::iter::once(HEADER.trim().replace("{{COMMENT}}", &comment))
+            .chain(std::iter::once(
+                STRUCT
+                    .trim()
+                    .replace(
+                        "{{NEW_DOC}}",
+                        if bound {
+                            " or not an instance of the original class"
+                        } else {
+                            ""
+                        },
+                    )
+                    .replace(
+                        "{{INSTANCE_CHECK}}",
+                        if bound { INSTANCE_CHECK } else { "" },
+                    )
+                    .replace("{{CLASS}}", object.classes().next().unwrap_or(&object.name))
+                    .replace("{{NAME}}", object.display_name())
+                    .replace("{{MEMBERS}}", &members.concat()),
+            ))
+            .chain(items)
+            .collect::<Vec<String>>()
+            .join("\n\n")
+            + "\n",
+    )
+}
+
+/// `mod.rs` of every directory up to `root`, declaring the generated modules and the directories
+/// below it.
+pub fn modules(sources: &[JavaSource], root: &Path) -> Vec<JavaSource> {
+    let mut modules: BTreeMap<PathBuf, BTreeSet<String>> = BTreeMap::new();
+
+    for source in sources {
+        let mut path = source.path.as_path();
+        while let (Some(parent), Some(name)) = (path.parent(), path.file_stem()) {
+            if !parent.starts_with(root) {
+                break;
+            }
+
+            modules
+                .entry(parent.to_path_buf())
+                .or_default()
+                .insert(name.to_string_lossy().into_owned());
+            path = parent;
+        }
+    }
+
+    modules
+        .into_iter()
+        .map(|(directory, names)| JavaSource {
+            path: directory.join(MODULE_FILE),
+            class_name: String::new(),
+            content: format!(
+                "// Modules autogenerated by the accessor generator. DO NOT EDIT\n\n{}\n",
+                names
+                    .iter()
+                    .map(|x| format!("pub mod {x};"))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            ),
+        })
+        .collect()
+}
+
+/// Name of the generated module, e.g. `living_entity_accessor` for `LivingEntity`
+pub fn module_name(name: &str) -> String {
+    format!("{}_accessor", snake_case(name))
+}
+
+/// `nestedBars` into `nested_bars`, names already in snake case are only lowercased
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut previous: Option<char> = None;
+
+    for char in name.chars() {
+        if char.is_uppercase() && previous.is_some_and(|x| x.is_lowercase() || x.is_ascii_digit()) {
+            snake.push('_');
+        }
+        snake.extend(char.to_lowercase());
+        previous = Some(char);
+    }
+
+    snake
+}
+
+/// Pieces of the getter and setter of a field, which differ between instance and static fields
+/// and between primitives and objects.
+struct Accessor<'a> {
+    field: &'a Field,
+    signature: &'a str,
+    /// Statement binding `class`, the class the field is looked up in
+    class: &'static str,
+    lookup: String,
+    receiver: &'static str,
+    target: &'static str,
+}
+
+impl<'a> Accessor<'a> {
+    fn new(field: &'a Field, cache: &str, signature: &'a str, bound: bool) -> Self {
+        let names = std::iter::once(&field.name)
+            .chain(&field.aliases)
+            .map(|x| format!("\"{x}\""))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let (class, lookup, receiver, target) = match (field.is_static, bound) {
+            (true, _) => (
+                "let class = target_class(env)?;",
+                "static_field_id(env, class, &{{CACHE}}",
+                "",
+                "class",
+            ),
+            // Field IDs of a class are valid for instances of its subclasses too
+            (false, true) => (
+                "let class = target_class(env)?;",
+                "field_id(env, class, Some(&{{CACHE}})",
+                "&self, ",
+                "&self.object",
+            ),
+            (false, false) => (
+                "let class = env.get_object_class(&self.object)?;",
+                "field_id(env, &class, None",
+                "&self, ",
+                "&self.object",
+            ),
+        };
+
+        Self {
+            field,
+            signature,
+            class,
+            lookup: format!(
+                "{}, &[{names}], \"{signature}\")",
+                lookup.replace("{{CACHE}}", cache)
+            ),
+            receiver,
+            target,
+        }
+    }
+
+    fn comment(&self) -> String {
+        match &self.field.rename {
+            Some(_) => format!("    /// Original name: {}\n", self.field.name),
+            None => String::new(),
+        }
+    }
+
+    /// Missing optional fields are skipped, returning `returned`
+    fn presence_check(&self, returned: &str) -> String {
+        if !self.field.optional {
+            return String::new();
+        }
+
+        let receiver = if self.field.is_static { "Self" } else { "self" };
+        let call = if self.field.is_static { "::" } else { "." };
+        format!(
+            "        if !{receiver}{call}has_{}(env)? {{\n            return Ok({returned});\n        }}\n\n",
+            snake_case(self.field.display_name()),
+        )
+    }
+
+    fn getter(&self) -> String {
+        let (rust_type, primitive, unpack, default) = match primitive(&self.field.field_type) {
+            Some((rust_type, primitive, _, unpack, default)) => (
+                rust_type.to_string(),
+                Some(primitive),
+                format!("{unpack}()"),
+                default.to_string(),
+            ),
+            None if matches!(self.field.field_type, Type::String) => (
+                "JString<'local>".to_string(),
+                None,
+                "l().map(JString::from)".to_string(),
+                "JString::from(JObject::null())".to_string(),
+            ),
+            None => (
+                "JObject<'local>".to_string(),
+                None,
+                "l()".to_string(),
+                "JObject::null()".to_string(),
+            ),
+        };
+
+        let (get, value_type) = match (self.field.is_static, primitive) {
+            (false, Some(x)) => (
+                "get_field_unchecked",
+                format!("ReturnType::Primitive(Primitive::{x})"),
+            ),
+            (false, None) => ("get_field_unchecked", "ReturnType::Object".to_string()),
+            (true, Some(x)) => (
+                "get_static_field_unchecked",
+                format!("JavaType::Primitive(Primitive::{x})"),
+            ),
+            (true, None) => (
+                "get_static_field_unchecked",
+                "JavaType::Object(String::new())".to_string(),
+            ),
+        };
+
+        GETTER
+            .replace("{{COMMENT}}", &self.comment())
+            .replace("{{PRESENCE_CHECK}}", &self.presence_check(&default))
+            .replace("{{CLASS}}", self.class)
+            .replace("{{LOOKUP}}", &self.lookup)
+            .replace("{{GET}}", get)
+            .replace("{{TARGET}}", self.target)
+            .replace("{{VALUE_TYPE}}", &value_type)
+            .replace("{{UNPACK}}", &unpack)
+            .replace("{{RUST_TYPE}}", &rust_type)
+            .replace("{{RECEIVER}}", self.receiver)
+            .replace("{{FIELD_NAME}}", &snake_case(self.field.display_name()))
+    }
+
+    fn setter(&self) -> String {
+        let (argument_type, value, value_check) = match primitive(&self.field.field_type) {
+            Some((rust_type, _, variant, _, _)) => (
+                rust_type.to_string(),
+                variant.replace("{}", "value"),
+                String::new(),
+            ),
+            None => (
+                "&JObject".to_string(),
+                "JValue::Object(value)".to_string(),
+                VALUE_CHECK
+                    .replace("{{VALUE_CLASS}}", &value_class(self.signature))
+                    .replace("{{SIGNATURE}}", self.signature),
+            ),
+        };
+
+        SETTER
+            .replace("{{COMMENT}}", &self.comment())
+            .replace("{{PRESENCE_CHECK}}", &self.presence_check("()"))
+            .replace("{{VALUE_CHECK}}", &value_check)
+            .replace("{{CLASS}}", self.class)
+            .replace("{{LOOKUP}}", &self.lookup)
+            .replace(
+                "{{SET}}",
+                if self.field.is_static {
+                    "set_static_field"
+                } else {
+                    "set_field_unchecked"
+                },
+            )
+            .replace("{{TARGET}}", self.target)
+            .replace("{{VALUE}}", &value)
+            .replace("{{ARGUMENT_TYPE}}", &argument_type)
+            .replace("{{RECEIVER}}", self.receiver)
+            .replace("{{FIELD_NAME}}", &snake_case(self.field.display_name()))
+    }
+
+    fn presence(&self) -> String {
+        PRESENCE
+            .replace("{{CLASS}}", self.class)
+            .replace("{{LOOKUP}}", &self.lookup)
+            .replace("{{RECEIVER}}", self.receiver)
+            .replace("{{FIELD_NAME}}", &snake_case(self.field.display_name()))
+    }
+}
+
+/// Name `find_class` takes for the class of a signature, `Lcom/x/Foo;` is `com/x/Foo` while arrays
+/// keep their descriptor
+fn value_class(signature: &str) -> String {
+    signature
+        .strip_prefix('L')
+        .and_then(|x| x.strip_suffix(';'))
+        .unwrap_or(signature)
+        .to_string()
+}
+
+/// Rust type, `Primitive` variant, `JValue` constructor, `JValueOwned` getter and default value
+/// of a primitive
+fn primitive(
+    field_type: &Type,
+) -> Option<(
+    &'static str,
+    &'static str,
+    &'static str,
+    &'static str,
+    &'static str,
+)> {
+    match field_type {
+        Type::Bool => Some((
+            "bool",
+            "Boolean",
+            "JValue::Bool(u8::from({}))",
+            "z",
+            "false",
+        )),
+        Type::I32 => Some(("i32", "Int", "JValue::Int({})", "i", "0")),
+        Type::I64 => Some(("i64", "Long", "JValue::Long({})", "j", "0")),
+        Type::U8 => Some(("i8", "Byte", "JValue::Byte({})", "b", "0")),
+        Type::I16 => Some(("i16", "Short", "JValue::Short({})", "s", "0")),
+        Type::F32 => Some(("f32", "Float", "JValue::Float({})", "f", "0.0")),
+        Type::F64 => Some(("f64", "Double", "JValue::Double({})", "d", "0.0")),
+        Type::Char => Some(("u16", "Char", "JValue::Char({})", "c", "0")),
+        _ => None,
+    }
+}
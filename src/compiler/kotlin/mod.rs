@@ -0,0 +1,819 @@
+use crate::{java::Visibility, kotlin, Field, Object};
+
+mod types;
+
+const ACCESS: &str = r#"
+if (original == null) return null
+
+val accessor = {{NAME}}Accessor(original)
+accessFields(original, accessor)
+return accessor
+"#;
+
+const ACCESS_START: &str = r#"
+if (original.javaClass.isEnum) throw RuntimeException("Failed to access {{NAME}}: Expected object to be object but got enum.")
+
+val clazz = {{CLASS}}
+{{PARENT}}"#;
+
+const ACCESS_FIELD: &str = r#"
+run {
+    val handle = {{LOOKUP}}
+    val value = handle.get(original)
+    accessor._{{FIELD_NAME}} = {{ACCESS}}
+}
+"#;
+
+// Missing optional fields are skipped, leaving their default value
+const ACCESS_OPTIONAL_FIELD: &str = r#"
+run {
+    val handle = try {
+        {{LOOKUP}}
+    } catch (e: NoSuchFieldException) {
+        null
+    }
+
+    if (handle != null) {
+        val value = handle.get(original)
+        accessor._{{FIELD_NAME}} = {{ACCESS}}
+        accessor.present_{{FIELD_NAME}} = true
+    }
+}
+"#;
+
+const GETTER: &str = r#"
+{{PRESENCE_CHECK}}val handle = {{LOOKUP}}
+val value = handle.get({{RECEIVER}})
+return {{ACCESS}}
+"#;
+
+const SETTER: &str = r#"
+{{PRESENCE_CHECK}}val handle = {{LOOKUP}}
+handle.set({{RECEIVER}}, {{ORIGINAL}})
+{{UPDATE_COPY}}"#;
+
+const SNAPSHOT_GETTER: &str = r#"
+return _{{FIELD_NAME}}
+"#;
+
+const PRESENCE_FLAG: &str = r#"
+return present_{{FIELD_NAME}}
+"#;
+
+const PRESENCE_LOOKUP: &str = r#"
+return try {
+    {{LOOKUP}}
+    true
+} catch (e: NoSuchFieldException) {
+    false
+}
+"#;
+
+const STATIC_PRESENCE_LOOKUP: &str = r#"
+return try {
+    {{LOOKUP}}
+    true
+} catch (e: ClassNotFoundException) {
+    false
+} catch (e: NoSuchFieldException) {
+    false
+}
+"#;
+
+const INVOKER: &str = r#"
+val method = findMethod({{CLASS}}, {{TRUE_METHOD_NAME}}, arrayOf<Class<*>?>({{PARAMETER_TYPES}}), {{HIERARCHY}})
+val value = method.invoke({{RECEIVER}}{{ARGUMENTS}})
+{{RETURN}}"#;
+
+const CLEAR_INNER_REFS_OF: &str = r#"
+when (value) {
+    null, is String, is Number, is Boolean, is Char, is Enum<*> -> return
+    is Array<*> -> value.forEach { clearInnerRefsOf(it) }
+    is Iterable<*> -> value.forEach { clearInnerRefsOf(it) }
+    is Map<*, *> -> value.forEach { (key, entry) ->
+        clearInnerRefsOf(key)
+        clearInnerRefsOf(entry)
+    }
+    is java.util.Optional<*> -> if (value.isPresent) clearInnerRefsOf(value.get())
+    else -> {
+        // Arrays of primitives (IntArray, ByteArray...) can't hold accessors
+        if (value.javaClass.isArray) return
+
+        try {
+            value.javaClass.getMethod("clearInnerRefs").invoke(value)
+        } catch (e: Exception) {
+            println("Error in clearInnerRefsOf:")
+            e.printStackTrace()
+        }
+    }
+}
+"#;
+
+const FIND_FIELD: &str = r#"
+// Fields are resolved once per runtime class, then reused by every access
+val fields = FIELDS.computeIfAbsent(clazz) { java.util.concurrent.ConcurrentHashMap<String, java.lang.reflect.Field>() }
+// The same name can be looked up up to different ancestors
+val key = if (hierarchy) names[0] + "^" + stopAt else names[0]
+fields[key]?.let { return it }
+
+// Every candidate is tried in a class before its superclass, the closest declaration wins
+var current: Class<*>? = clazz
+while (current != null) {
+    for (name in names) {
+        try {
+            val field = current.getDeclaredField(name)
+            field.isAccessible = true
+            fields[key] = field
+            return field
+        } catch (e: NoSuchFieldException) {
+            // Try the next candidate
+        }
+    }
+
+    if (current.name == stopAt) break
+    current = if (hierarchy) current.superclass else null
+}
+
+throw NoSuchFieldException(clazz.name + " has none of the fields: " + names.joinToString(", "))
+"#;
+
+const FIND_METHOD: &str = r#"
+// Methods are resolved once per runtime class, then reused by every call
+val methods = METHODS.computeIfAbsent(clazz) { java.util.concurrent.ConcurrentHashMap<String, java.lang.reflect.Method>() }
+val key = name + parameters.contentToString()
+methods[key]?.let { return it }
+
+var current: Class<*>? = clazz
+while (current != null) {
+    for (method in current.declaredMethods) {
+        if (method.name != name || method.parameterCount != parameters.size) continue
+
+        // Parameters whose original type is unknown (accessors) are null, and match any non primitive
+        val types = method.parameterTypes
+        val matches = parameters.indices.all { i ->
+            val parameter = parameters[i]
+            if (parameter == null) !types[i].isPrimitive else parameter == types[i]
+        }
+
+        if (matches) {
+            method.isAccessible = true
+            methods[key] = method
+            return method
+        }
+    }
+
+    current = if (hierarchy) current.superclass else null
+}
+
+throw NoSuchMethodException(clazz.name + "." + name + " with " + parameters.size + " parameters")
+"#;
+
+const TARGET_CLASS: &str = r#"
+for (name in arrayOf({{CLASSES}})) {
+    try {
+        return Class.forName(name)
+    } catch (e: ClassNotFoundException) {
+        // Try the next alternate
+    }
+}
+
+throw ClassNotFoundException("{{NAME}}Accessor could not find any of its original classes: " + {{CLASS_NAMES}})
+"#;
+
+const MATCH_TARGET_CLASS: &str = r#"
+var current: Class<*>? = clazz
+while (current != null) {
+    when (current.name) {
+        {{CLASSES}} -> return current
+    }
+    current = current.superclass
+}
+
+throw IllegalArgumentException("{{NAME}}Accessor expected an instance of " + {{CLASS_NAMES}} + " but got " + clazz.name)
+"#;
+
+const ENUM_ACCESS: &str = r#"
+if (original == null) return null
+if (original !is Enum<*>) throw RuntimeException("{{NAME}} was supposed to be an enum but it is not!")
+
+// Constants with a body are subclasses of their enum
+val declaring: Class<*> = if (original.javaClass.isEnum) original.javaClass else original.javaClass.superclass
+originalClass = {{ENUM_CLASS}}
+
+return when (original.name) {
+{{CASES}}
+    else -> throw RuntimeException("{{NAME}}Accessor has an unrecognized variant: '" + original.name + "'")
+}
+"#;
+
+const ENUM_TO_ORIGINAL: &str = r#"
+// The declared type can be less specific than the enum, e.g. a field of type Object
+if (enumClass == null || !enumClass.isEnum) return unwrap()
+
+val originals = ORIGINALS.computeIfAbsent(enumClass) { arrayOfNulls<Any>(values().size) }
+originals[ordinal]?.let { return it }
+
+// Name first, then aliases in the order they were declared
+for (candidate in names) {
+    for (constant in enumClass.enumConstants) {
+        if ((constant as Enum<*>).name == candidate) {
+            originals[ordinal] = constant
+            return constant
+        }
+    }
+}
+
+throw RuntimeException("{{NAME}}Accessor." + name + " has no matching constant in " + enumClass.name + ", tried: " + names.joinToString(", "))
+"#;
+
+const ENUM_UNWRAP: &str = r#"
+val enumClass = originalClass ?: throw RuntimeException("{{NAME}}Accessor." + name + " can't be unwrapped before any {{NAME}} was accessed, use toOriginal instead.")
+return toOriginal(enumClass)
+"#;
+
+// Bound enums can be loaded even if none was accessed yet
+const BOUND_ENUM_UNWRAP: &str = r#"
+return toOriginal(originalClass ?: targetClass())
+"#;
+
+/// Kotlin accessor class of the object. Fields are `var` properties whose setters write the
+/// original object, static members and `access` live in the companion object.
+pub fn generate_class(object: &Object, includes: &[Object], comment: String) -> String {
+    let mut properties = vec![];
+    let mut functions = vec![];
+    let mut companion_properties = vec![];
+    let mut companion_functions = access(object);
+
+    for field in object.instance_fields() {
+        if !object.is_live() {
+            properties.push(
+                kotlin::Property::builder()
+                    .name(format!("_{}", field.display_name()))
+                    .type_name(types::type_name(&field.field_type, object))
+                    .visibility(Visibility::Private)
+                    .is_mutable(true)
+                    .value(types::default_value(&field.field_type))
+                    .build(),
+            );
+
+            if field.optional {
+                properties.push(
+                    kotlin::Property::builder()
+                        .name(format!("present_{}", field.display_name()))
+                        .type_name("Boolean")
+                        .visibility(Visibility::Private)
+                        .is_mutable(true)
+                        .value("false")
+                        .build(),
+                );
+            }
+        }
+
+        properties.push(property(object, includes, field));
+    }
+
+    for field in object.static_fields() {
+        companion_properties.push(property(object, includes, field));
+    }
+
+    for field in object.fields.iter().filter(|x| x.optional) {
+        let function = presence_check(object, field);
+        if field.is_static {
+            companion_functions.push(function);
+        } else {
+            functions.push(function);
+        }
+    }
+
+    for method in &object.methods {
+        let function = invoker(object, includes, method);
+        if method.is_static {
+            companion_functions.push(function);
+        } else {
+            functions.push(function);
+        }
+    }
+
+    // Accessors extending another one inherit the object
+    if object.extends.is_none() {
+        functions.push(
+            kotlin::Function::builder()
+                .name("unwrap")
+                .return_type("Any?")
+                .body("return self")
+                .build(),
+        );
+    }
+    functions.push(clear_inner_refs(object, includes));
+
+    companion_functions.push(
+        kotlin::Function::builder()
+            .name("clearInnerRefsOf")
+            .visibility(Visibility::Private)
+            .parameters(vec![kotlin::Parameter::builder()
+                .name("value")
+                .type_name("Any?")
+                .build()])
+            .body(CLEAR_INNER_REFS_OF)
+            .build(),
+    );
+
+    if !object.fields.is_empty() {
+        companion_properties.push(cache("FIELDS", "java.lang.reflect.Field"));
+        companion_functions.push(
+            kotlin::Function::builder()
+                .name("findField")
+                .visibility(Visibility::Private)
+                .parameters(vec![
+                    parameter("clazz", "Class<*>"),
+                    parameter("names", "Array<String>"),
+                    parameter("hierarchy", "Boolean"),
+                    parameter("stopAt", "String?"),
+                ])
+                .return_type("java.lang.reflect.Field")
+                .body(FIND_FIELD)
+                .build(),
+        );
+    }
+
+    if !object.methods.is_empty() {
+        companion_properties.push(cache("METHODS", "java.lang.reflect.Method"));
+        companion_functions.push(
+            kotlin::Function::builder()
+                .name("findMethod")
+                .visibility(Visibility::Private)
+                .parameters(vec![
+                    parameter("clazz", "Class<*>"),
+                    parameter("name", "String"),
+                    parameter("parameters", "Array<Class<*>?>"),
+                    parameter("hierarchy", "Boolean"),
+                ])
+                .return_type("java.lang.reflect.Method")
+                .body(FIND_METHOD)
+                .build(),
+        );
+    }
+
+    companion_functions.extend(target_class(object));
+
+    kotlin::Class::builder()
+        .name(format!("{}Accessor", object.display_name()))
+        .package(&object.package)
+        .comment(comment)
+        .includes(super::imports(object, includes))
+        .parameters(match object.extends {
+            Some(_) => vec![parameter("self", "Any?")],
+            None => vec![],
+        })
+        .constructor(match object.extends {
+            Some(_) => vec![],
+            None => vec![kotlin::Property::builder()
+                .name("self")
+                .type_name("Any?")
+                .visibility(Visibility::Protected)
+                .is_mutable(true)
+                .build()],
+        })
+        .maybe_superclass(
+            object
+                .extends
+                .as_ref()
+                .map(|parent| format!("{parent}Accessor(self)")),
+        )
+        .properties(properties)
+        .functions(functions)
+        .companion_properties(companion_properties)
+        .companion_functions(companion_functions)
+        .build()
+        .source()
+}
+
+/// Kotlin `enum class` of the object, converting from and into the original enum like the Java
+/// one.
+pub fn generate_enum(object: &Object, includes: &[Object], comment: String) -> String {
+    let name = object.display_name();
+
+    let cases = object
+        .variants
+        .iter()
+        .map(|variant| {
+            let labels = std::iter::once(&variant.name)
+                .chain(&variant.aliases)
+                .map(|x| types::string(x))
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!("    {labels} -> {}", variant.display_name())
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let bound = object.classes().next().is_some();
+
+    kotlin::Enum::builder()
+        .name(format!("{name}Accessor"))
+        .package(&object.package)
+        .comment(comment)
+        .includes(super::imports(object, includes))
+        .constructor(vec![kotlin::Property::builder()
+            .name("names")
+            .type_name("Array<String>")
+            .visibility(Visibility::Private)
+            .build()])
+        .variants(
+            object
+                .variants
+                .iter()
+                .map(|x| {
+                    let names = std::iter::once(&x.name)
+                        .chain(&x.aliases)
+                        .map(|name| types::string(name))
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    format!("{}(arrayOf({names}))", x.display_name())
+                })
+                .collect(),
+        )
+        .functions(vec![
+            kotlin::Function::builder()
+                .name("toOriginal")
+                .parameters(vec![parameter("enumClass", "Class<*>?")])
+                .return_type("Any?")
+                .body(ENUM_TO_ORIGINAL.replace("{{NAME}}", name))
+                .build(),
+            kotlin::Function::builder()
+                .name("unwrap")
+                .return_type("Any?")
+                .body(
+                    if bound {
+                        BOUND_ENUM_UNWRAP
+                    } else {
+                        ENUM_UNWRAP
+                    }
+                    .replace("{{NAME}}", name),
+                )
+                .build(),
+        ])
+        .companion_properties(vec![
+            kotlin::Property::builder()
+                .name("originalClass")
+                .type_name("Class<*>?")
+                .comment("Enum class seen by the last call to access, used by unwrap")
+                .annotations(vec!["@Volatile".to_string()])
+                .visibility(Visibility::Private)
+                .is_mutable(true)
+                .value("null")
+                .build(),
+            kotlin::Property::builder()
+                .name("ORIGINALS")
+                .type_name("java.util.concurrent.ConcurrentHashMap<Class<*>, Array<Any?>>")
+                .visibility(Visibility::Private)
+                .value("java.util.concurrent.ConcurrentHashMap()")
+                .build(),
+        ])
+        .companion_functions(
+            std::iter::once(
+                kotlin::Function::builder()
+                    .name("access")
+                    .annotations(vec!["@JvmStatic".to_string()])
+                    .parameters(vec![parameter("original", "Any?")])
+                    .return_type(format!("{name}Accessor?"))
+                    .body(
+                        ENUM_ACCESS
+                            .replace("{{CASES}}", &cases)
+                            .replace(
+                                "{{ENUM_CLASS}}",
+                                if bound {
+                                    "matchTargetClass(declaring)"
+                                } else {
+                                    "declaring"
+                                },
+                            )
+                            .replace("{{NAME}}", name),
+                    )
+                    .build(),
+            )
+            .chain(target_class(object))
+            .collect(),
+        )
+        .build()
+        .source()
+}
+
+fn parameter(name: &str, type_name: &str) -> kotlin::Parameter {
+    kotlin::Parameter::builder()
+        .name(name)
+        .type_name(type_name)
+        .build()
+}
+
+/// Handles resolved once per runtime class
+fn cache(name: &str, handle: &str) -> kotlin::Property {
+    kotlin::Property::builder()
+        .name(name)
+        .type_name(format!(
+            "java.util.concurrent.ConcurrentHashMap<Class<*>, MutableMap<String, {handle}>>"
+        ))
+        .visibility(Visibility::Private)
+        .value("java.util.concurrent.ConcurrentHashMap()")
+        .build()
+}
+
+/// `access`, and `accessFields` which fills the fields of an accessor that was already created,
+/// see [`super::constructs::single_class_accessor`].
+fn access(object: &Object) -> Vec<kotlin::Function> {
+    let fields = object
+        .instance_fields()
+        // Live accessors read the fields through their getters instead
+        .filter(|_| !object.is_live())
+        .map(|field| {
+            if field.optional {
+                ACCESS_OPTIONAL_FIELD
+            } else {
+                ACCESS_FIELD
+            }
+            .replace("{{LOOKUP}}", &types::lookup(field, "clazz"))
+            .replace("{{FIELD_NAME}}", field.display_name())
+            .replace(
+                "{{ACCESS}}",
+                &types::access(&field.field_type, "value", object, 0),
+            )
+        });
+
+    let body = std::iter::once(
+        ACCESS_START
+            .replace("{{CLASS}}", &types::instance_class(object, "original"))
+            .replace(
+                "{{PARENT}}",
+                &match &object.extends {
+                    Some(parent) => format!("{parent}Accessor.accessFields(original, accessor)"),
+                    None => String::new(),
+                },
+            )
+            .replace("{{NAME}}", object.display_name()),
+    )
+    .chain(fields)
+    .map(|x| x.trim().to_string())
+    .collect::<Vec<String>>()
+    .join("\n\n");
+
+    vec![
+        kotlin::Function::builder()
+            .name("access")
+            .annotations(vec!["@JvmStatic".to_string()])
+            .parameters(vec![parameter("original", "Any?")])
+            .return_type(format!("{}Accessor?", object.display_name()))
+            .body(ACCESS.replace("{{NAME}}", object.display_name()))
+            .build(),
+        kotlin::Function::builder()
+            .name("accessFields")
+            .visibility(Visibility::Protected)
+            .parameters(vec![
+                parameter("original", "Any"),
+                parameter("accessor", &format!("{}Accessor", object.display_name())),
+            ])
+            .body(body)
+            .build(),
+    ]
+}
+
+/// Property of a field. Snapshots read the copy made by `access`, live accessors and static fields
+/// read the original on every call. Setters always write the original.
+fn property(object: &Object, includes: &[Object], field: &Field) -> kotlin::Property {
+    let (class, receiver) = if field.is_static {
+        ("targetClass()".to_string(), "null")
+    } else {
+        (types::instance_class(object, "self!!"), "self")
+    };
+    let lookup = types::lookup(field, &class);
+    let presence_check = |returned: &str| {
+        if field.optional {
+            format!(
+                "if (!has{}()) return{returned}\n\n",
+                super::capitalize(field.display_name())
+            )
+        } else {
+            String::new()
+        }
+    };
+
+    let getter = if field.is_static || object.is_live() {
+        GETTER
+            .replace(
+                "{{PRESENCE_CHECK}}",
+                &presence_check(&format!(" {}", types::default_value(&field.field_type))),
+            )
+            .replace("{{LOOKUP}}", &lookup)
+            .replace("{{RECEIVER}}", receiver)
+            .replace(
+                "{{ACCESS}}",
+                &types::access(&field.field_type, "value", object, 0),
+            )
+    } else {
+        SNAPSHOT_GETTER.replace("{{FIELD_NAME}}", field.display_name())
+    };
+
+    let setter = SETTER
+        .replace("{{PRESENCE_CHECK}}", &presence_check(""))
+        .replace("{{LOOKUP}}", &lookup)
+        .replace("{{RECEIVER}}", receiver)
+        .replace(
+            "{{ORIGINAL}}",
+            &types::unwrap(
+                &field.field_type,
+                "value",
+                Some("handle.type"),
+                object,
+                includes,
+                0,
+            ),
+        )
+        .replace(
+            "{{UPDATE_COPY}}",
+            &if field.is_static || object.is_live() {
+                String::new()
+            } else {
+                format!("_{} = value\n", field.display_name())
+            },
+        );
+
+    kotlin::Property::builder()
+        .name(field.display_name())
+        .type_name(types::type_name(&field.field_type, object))
+        .maybe_comment(if field.rename.is_some() {
+            Some(format!("Original name: {}", field.name))
+        } else {
+            None
+        })
+        .is_mutable(true)
+        .getter(getter)
+        .setter(setter)
+        .build()
+}
+
+/// `has<Field>()` of an optional field
+fn presence_check(object: &Object, field: &Field) -> kotlin::Function {
+    let body = if field.is_static {
+        STATIC_PRESENCE_LOOKUP.replace("{{LOOKUP}}", &types::lookup(field, "targetClass()"))
+    } else if object.is_live() {
+        PRESENCE_LOOKUP.replace(
+            "{{LOOKUP}}",
+            &types::lookup(field, &types::instance_class(object, "self!!")),
+        )
+    } else {
+        PRESENCE_FLAG.replace("{{FIELD_NAME}}", field.display_name())
+    };
+
+    kotlin::Function::builder()
+        .name(format!("has{}", super::capitalize(field.display_name())))
+        .return_type("Boolean")
+        .body(body)
+        .build()
+}
+
+fn invoker(object: &Object, includes: &[Object], method: &crate::Method) -> kotlin::Function {
+    let (class, receiver) = if method.is_static {
+        ("targetClass()".to_string(), "null")
+    } else {
+        (types::instance_class(object, "self!!"), "self")
+    };
+
+    let parameter_types = method
+        .parameters
+        .iter()
+        .map(|x| types::class_literal(x).unwrap_or("null".to_string()))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    // Accessors are unwrapped into the original values, the real parameter types are used to
+    // resolve enums and create arrays
+    let arguments = method
+        .parameters
+        .iter()
+        .enumerate()
+        .map(|(i, parameter)| {
+            format!(
+                ", {}",
+                types::unwrap(
+                    parameter,
+                    &format!("arg{i}"),
+                    Some(&format!("method.parameterTypes[{i}]")),
+                    object,
+                    includes,
+                    0,
+                )
+            )
+        })
+        .collect::<String>();
+
+    let body = INVOKER
+        .replace("{{CLASS}}", &class)
+        .replace("{{TRUE_METHOD_NAME}}", &types::string(&method.name))
+        .replace("{{PARAMETER_TYPES}}", &parameter_types)
+        .replace("{{HIERARCHY}}", &method.hierarchy.to_string())
+        .replace("{{RECEIVER}}", receiver)
+        .replace("{{ARGUMENTS}}", &arguments)
+        .replace(
+            "{{RETURN}}",
+            &match &method.return_type {
+                Some(x) => format!("return {}\n", types::access(x, "value", object, 0)),
+                None => String::new(),
+            },
+        );
+
+    kotlin::Function::builder()
+        .name(method.display_name())
+        .parameters(
+            method
+                .parameters
+                .iter()
+                .enumerate()
+                .map(|(i, x)| parameter(&format!("arg{i}"), &types::type_name(x, object)))
+                .collect(),
+        )
+        .maybe_return_type(
+            method
+                .return_type
+                .as_ref()
+                .map(|x| types::type_name(x, object)),
+        )
+        .body(body)
+        .build()
+}
+
+fn clear_inner_refs(object: &Object, includes: &[Object]) -> kotlin::Function {
+    // Live accessors don't hold any other accessor, neither do primitives and enums
+    let fields = object
+        .instance_fields()
+        .filter(|field| {
+            let objects = field.field_type.objects(object);
+            let is_enum = !objects.is_empty()
+                && objects
+                    .iter()
+                    .all(|name| includes.iter().any(|x| x.is_named(name) && x.is_enum()));
+
+            !object.is_live() && !field.field_type.is_primitive() && !is_enum
+        })
+        .map(|field| format!("clearInnerRefsOf(_{})", field.display_name()));
+
+    kotlin::Function::builder()
+        .name("clearInnerRefs")
+        .is_open(true)
+        .is_override(object.extends.is_some())
+        .body(
+            // The inherited fields are cleared by the accessor being extended
+            std::iter::once(match object.extends {
+                Some(_) => "super.clearInnerRefs()".to_string(),
+                None => "self = null".to_string(),
+            })
+            .chain(fields)
+            .collect::<Vec<String>>()
+            .join("\n"),
+        )
+        .build()
+}
+
+/// `targetClass` and `matchTargetClass`, see [`super::constructs::target_class`]
+fn target_class(object: &Object) -> Vec<kotlin::Function> {
+    let classes = object
+        .classes()
+        .map(|x| types::string(x))
+        .collect::<Vec<String>>();
+    if classes.is_empty() {
+        return vec![];
+    }
+
+    let class_names = types::string(
+        &object
+            .classes()
+            .map(|x| x.as_str())
+            .collect::<Vec<&str>>()
+            .join(", "),
+    );
+
+    vec![
+        kotlin::Function::builder()
+            .name("targetClass")
+            // Other accessors create arrays of the original class with it
+            .visibility(Visibility::Public)
+            .return_type("Class<*>")
+            .body(
+                TARGET_CLASS
+                    .replace("{{CLASSES}}", &classes.join(", "))
+                    .replace("{{CLASS_NAMES}}", &class_names)
+                    .replace("{{NAME}}", object.display_name()),
+            )
+            .build(),
+        kotlin::Function::builder()
+            .name("matchTargetClass")
+            .visibility(Visibility::Private)
+            .parameters(vec![parameter("clazz", "Class<*>")])
+            .return_type("Class<*>")
+            .body(
+                MATCH_TARGET_CLASS
+                    .replace("{{CLASSES}}", &classes.join(", "))
+                    .replace("{{CLASS_NAMES}}", &class_names)
+                    .replace("{{NAME}}", object.display_name()),
+            )
+            .build(),
+    ]
+}
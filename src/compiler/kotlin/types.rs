@@ -0,0 +1,297 @@
+use crate::{Field, Object, Type};
+
+/// Kotlin string literal of `value`, `$` would otherwise start a template
+pub fn string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$")
+    )
+}
+
+/// Kotlin expression looking up the `java.lang.reflect.Field` in `class`, trying every candidate
+/// name.
+pub fn lookup(field: &Field, class: &str) -> String {
+    let names = std::iter::once(&field.name)
+        .chain(&field.aliases)
+        .map(|x| string(x))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let stop_at = match &field.stop_at {
+        Some(ancestor) => string(ancestor),
+        None => "null".to_string(),
+    };
+
+    format!(
+        "findField({class}, arrayOf({names}), {}, {stop_at})",
+        field.hierarchy
+    )
+}
+
+/// Kotlin expression of the class the members of `instance` are looked up in, see
+/// [`Object::instance_class`].
+pub fn instance_class(object: &Object, instance: &str) -> String {
+    if object.classes().next().is_some() {
+        format!("matchTargetClass({instance}.javaClass)")
+    } else {
+        format!("{instance}.javaClass")
+    }
+}
+
+/// Kotlin arrays of primitives, which aren't generic
+fn primitive_array(inner: &Type) -> Option<&'static str> {
+    match inner {
+        Type::Bool => Some("BooleanArray"),
+        Type::I32 => Some("IntArray"),
+        Type::I64 => Some("LongArray"),
+        Type::U8 => Some("ByteArray"),
+        Type::I16 => Some("ShortArray"),
+        Type::F32 => Some("FloatArray"),
+        Type::F64 => Some("DoubleArray"),
+        Type::Char => Some("CharArray"),
+        _ => None,
+    }
+}
+
+/// Kotlin type of an accessor property. Everything but primitives can be null.
+pub fn type_name(field_type: &Type, object: &Object) -> String {
+    match field_type {
+        Type::Bool => "Boolean".to_string(),
+        Type::I32 => "Int".to_string(),
+        Type::I64 => "Long".to_string(),
+        Type::U8 => "Byte".to_string(),
+        Type::I16 => "Short".to_string(),
+        Type::F32 => "Float".to_string(),
+        Type::F64 => "Double".to_string(),
+        Type::Char => "Char".to_string(),
+        Type::String => "String?".to_string(),
+        Type::Boolean => "Boolean?".to_string(),
+        Type::Integer => "Int?".to_string(),
+        Type::Long => "Long?".to_string(),
+        Type::Byte => "Byte?".to_string(),
+        Type::Short => "Short?".to_string(),
+        Type::Float => "Float?".to_string(),
+        Type::Double => "Double?".to_string(),
+        Type::Character => "Char?".to_string(),
+        Type::Object(name) => format!("{name}Accessor?"),
+        Type::SelfObject => format!("{}Accessor?", object.display_name()),
+        Type::Array(inner) => match primitive_array(inner) {
+            Some(array) => format!("{array}?"),
+            None => format!("Array<{}>?", generic_name(inner, object)),
+        },
+        Type::List(inner) => format!("List<{}>?", generic_name(inner, object)),
+        Type::Set(inner) => format!("Set<{}>?", generic_name(inner, object)),
+        Type::Map(key, value) => format!(
+            "Map<{}, {}>?",
+            generic_name(key, object),
+            generic_name(value, object)
+        ),
+        Type::Optional(inner) => format!("java.util.Optional<{}>?", generic_name(inner, object)),
+    }
+}
+
+/// Kotlin type when used as a generic argument, where primitives are boxed and can be null.
+fn generic_name(field_type: &Type, object: &Object) -> String {
+    let name = type_name(field_type, object);
+    if name.ends_with('?') {
+        name
+    } else {
+        format!("{name}?")
+    }
+}
+
+/// Kotlin default value of the type, what a missing optional field holds.
+pub fn default_value(field_type: &Type) -> &'static str {
+    match field_type {
+        Type::Bool => "false",
+        Type::I32 | Type::U8 | Type::I16 => "0",
+        Type::I64 => "0L",
+        Type::F32 => "0f",
+        Type::F64 => "0.0",
+        Type::Char => "'\\u0000'",
+        _ => "null",
+    }
+}
+
+/// Kotlin expression of the Java class of the type, when it's the same for the original value and
+/// the accessor. See [`Type::java_class`].
+pub fn class_literal(field_type: &Type) -> Option<String> {
+    let primitive = |name: &str| Some(format!("{name}::class.javaPrimitiveType!!"));
+    let boxed = |name: &str| Some(format!("{name}::class.javaObjectType"));
+
+    match field_type {
+        Type::Bool => primitive("Boolean"),
+        Type::I32 => primitive("Int"),
+        Type::I64 => primitive("Long"),
+        Type::U8 => primitive("Byte"),
+        Type::I16 => primitive("Short"),
+        Type::F32 => primitive("Float"),
+        Type::F64 => primitive("Double"),
+        Type::Char => primitive("Char"),
+        Type::String => Some("String::class.java".to_string()),
+        Type::Boolean => boxed("Boolean"),
+        Type::Integer => boxed("Int"),
+        Type::Long => boxed("Long"),
+        Type::Byte => boxed("Byte"),
+        Type::Short => boxed("Short"),
+        Type::Float => boxed("Float"),
+        Type::Double => boxed("Double"),
+        Type::Character => boxed("Char"),
+        // Kotlin has no literal for arrays of arbitrary depth, an empty one is made instead
+        Type::Array(inner) => class_literal(inner)
+            .map(|x| format!("java.lang.reflect.Array.newInstance({x}, 0).javaClass")),
+        Type::Object(_)
+        | Type::SelfObject
+        | Type::List(_)
+        | Type::Set(_)
+        | Type::Map(_, _)
+        | Type::Optional(_) => None,
+    }
+}
+
+/// Kotlin expression of the Java class of the original values of the type, when it's known
+/// without an original value. See [`Type::original_class`].
+fn original_class(field_type: &Type, object: &Object, includes: &[Object]) -> Option<String> {
+    let target = |target: &Object| {
+        target
+            .classes()
+            .next()
+            .map(|_| format!("{}Accessor.targetClass()", target.display_name()))
+    };
+
+    match field_type {
+        Type::SelfObject => target(object),
+        Type::Object(name) if object.is_named(name) => target(object),
+        Type::Object(name) => includes.iter().find(|x| x.is_named(name)).and_then(target),
+        Type::Array(inner) => original_class(inner, object, includes)
+            .map(|x| format!("java.lang.reflect.Array.newInstance({x}, 0).javaClass")),
+        Type::List(_) => Some("java.util.List::class.java".to_string()),
+        Type::Set(_) => Some("java.util.Set::class.java".to_string()),
+        Type::Map(_, _) => Some("java.util.Map::class.java".to_string()),
+        Type::Optional(_) => Some("java.util.Optional::class.java".to_string()),
+        other => class_literal(other),
+    }
+}
+
+/// Kotlin expression turning `value`, read from the original object, into the accessor value.
+/// * `depth` - Nesting of the expression, keeps the names of the lambda parameters apart
+pub fn access(field_type: &Type, value: &str, object: &Object, depth: usize) -> String {
+    let entry = format!("e{depth}");
+
+    match field_type {
+        Type::Object(name) => format!("{name}Accessor.access({value})"),
+        Type::SelfObject => format!("{}Accessor.access({value})", object.display_name()),
+        Type::Array(inner) => match primitive_array(inner) {
+            Some(array) => format!("({value} as {array}?)"),
+            None => format!(
+                "({value} as Array<*>?)?.map {{ {entry} -> {} }}?.toTypedArray<{}>()",
+                access(inner, &entry, object, depth + 1),
+                generic_name(inner, object)
+            ),
+        },
+        Type::List(inner) => format!(
+            "({value} as List<*>?)?.map {{ {entry} -> {} }}",
+            access(inner, &entry, object, depth + 1)
+        ),
+        Type::Set(inner) => format!(
+            "({value} as Set<*>?)?.map {{ {entry} -> {} }}?.toSet()",
+            access(inner, &entry, object, depth + 1)
+        ),
+        Type::Map(key, value_type) => format!(
+            "({value} as Map<*, *>?)?.entries?.associate {{ {entry} -> Pair<{}, {}>({}, {}) }}",
+            generic_name(key, object),
+            generic_name(value_type, object),
+            access(key, &format!("{entry}.key"), object, depth + 1),
+            access(value_type, &format!("{entry}.value"), object, depth + 1)
+        ),
+        Type::Optional(inner) => {
+            let optional = format!("o{depth}");
+            let generic = generic_name(inner, object);
+            format!(
+                "({value} as java.util.Optional<*>?)?.let {{ {optional} -> if ({optional}.isPresent) java.util.Optional.ofNullable<{generic}>({}) else java.util.Optional.empty<{generic}>() }}",
+                access(inner, &format!("{optional}.get()"), object, depth + 1)
+            )
+        }
+        other => format!("({value} as {})", type_name(other, object)),
+    }
+}
+
+/// Kotlin expression turning `value`, an accessor value, back into the value the original object
+/// holds. See [`Type::unwrap_expression`].
+pub fn unwrap(
+    field_type: &Type,
+    value: &str,
+    class: Option<&str>,
+    object: &Object,
+    includes: &[Object],
+    depth: usize,
+) -> String {
+    let entry = format!("e{depth}");
+
+    match field_type {
+        Type::Object(name) => {
+            let is_enum = includes.iter().any(|x| x.is_named(name) && x.is_enum());
+            match class {
+                Some(class) if is_enum => format!("{value}?.toOriginal({class})"),
+                _ => format!("{value}?.unwrap()"),
+            }
+        }
+        Type::SelfObject => format!("{value}?.unwrap()"),
+        // Primitives, strings and arrays of them are the same values
+        other if other.is_primitive() => value.to_string(),
+        Type::Array(inner) => {
+            let (array, result, index) = (
+                format!("a{depth}"),
+                format!("r{depth}"),
+                format!("i{depth}"),
+            );
+            // Inside of collections there is no original array to take the class from
+            let component = class
+                .map(|x| format!("{x}.componentType"))
+                .or_else(|| original_class(inner, object, includes));
+            format!(
+                "{value}?.let {{ {array} -> java.lang.reflect.Array.newInstance({}, {array}.size).also {{ {result} -> {array}.forEachIndexed {{ {index}, {entry} -> java.lang.reflect.Array.set({result}, {index}, {}) }} }} }}",
+                component.as_deref().unwrap_or("Any::class.java"),
+                unwrap(inner, &entry, component.as_deref(), object, includes, depth + 1)
+            )
+        }
+        Type::List(inner) => format!(
+            "{value}?.map {{ {entry} -> {} }}",
+            unwrap(inner, &entry, None, object, includes, depth + 1)
+        ),
+        Type::Set(inner) => format!(
+            "{value}?.map {{ {entry} -> {} }}?.toSet()",
+            unwrap(inner, &entry, None, object, includes, depth + 1)
+        ),
+        Type::Map(key, value_type) => format!(
+            "{value}?.entries?.associate {{ {entry} -> Pair<Any?, Any?>({}, {}) }}",
+            unwrap(
+                key,
+                &format!("{entry}.key"),
+                None,
+                object,
+                includes,
+                depth + 1
+            ),
+            unwrap(
+                value_type,
+                &format!("{entry}.value"),
+                None,
+                object,
+                includes,
+                depth + 1
+            )
+        ),
+        Type::Optional(inner) => {
+            let optional = format!("o{depth}");
+            format!(
+                "{value}?.let {{ {optional} -> if ({optional}.isPresent) java.util.Optional.ofNullable<Any>({}) else java.util.Optional.empty<Any>() }}",
+                unwrap(inner, &format!("{optional}.get()"), None, object, includes, depth + 1)
+            )
+        }
+        _ => value.to_string(),
+    }
+}
@@ -0,0 +1,401 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::Object;
+
+use super::SourceError;
+
+/// Names of a class and its members, from the readable names used in the schemas to the names
+/// the class has at runtime.
+#[derive(Debug, Default)]
+struct ClassMapping {
+    runtime: String,
+    fields: HashMap<String, String>,
+    /// Keyed by name and number of parameters, to tell apart most overloads
+    methods: HashMap<(String, usize), String>,
+}
+
+/// Obfuscation mappings loaded from a ProGuard (Mojang) or Tiny v2 file.
+#[derive(Debug)]
+pub struct Mappings {
+    pub path: PathBuf,
+    /// Keyed by the fully qualified readable name of the class
+    classes: HashMap<String, ClassMapping>,
+}
+
+impl Mappings {
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, SourceError> {
+        let path = path.into();
+        let source = std::fs::read_to_string(&path).map_err(SourceError::IO)?;
+
+        let classes = if source.starts_with("tiny\t2\t") {
+            parse_tiny(&source)?
+        } else {
+            parse_proguard(&source)?
+        };
+
+        Ok(Self { path, classes })
+    }
+
+    /// Rewrites the names of the object and its members into the runtime names. The readable names
+    /// are kept as renames, so the generated accessors keep them.
+    /// Alternate classes and aliases are rewritten too when the mappings have them, otherwise
+    /// they are expected to be runtime names already, e.g. of another version of the library.
+    /// False when the mappings don't have the class of the object, which is left as is.
+    pub fn apply(&self, object: &mut Object) -> bool {
+        let Some((readable, mapping)) = self.find_class(object) else {
+            return false;
+        };
+
+        tracing::debug!("Mapping '{}' to '{}'", readable, mapping.runtime);
+
+        if object.rename.is_none() {
+            object.rename = Some(object.name.clone());
+        }
+        object.name = simple_name(&mapping.runtime).to_string();
+        object.class_name = Some(mapping.runtime.clone());
+        for class in &mut object.alternate_classes {
+            if let Some(runtime) = self.classes.get(class.as_str()) {
+                *class = runtime.runtime.clone();
+            }
+        }
+
+        for field in &mut object.fields {
+            if let Some(runtime) = mapping.fields.get(&field.name) {
+                if field.rename.is_none() {
+                    field.rename = Some(field.name.clone());
+                }
+                field.name = runtime.clone();
+            }
+            map_aliases(&mut field.aliases, &mapping.fields);
+
+            if let Some(ancestor) = &mut field.stop_at {
+                if let Some(runtime) = self.classes.get(ancestor.as_str()) {
+                    *ancestor = runtime.runtime.clone();
+                }
+            }
+        }
+
+        // Enum constants are mapped as fields of the enum class
+        for variant in &mut object.variants {
+            if let Some(runtime) = mapping.fields.get(&variant.name) {
+                if variant.rename.is_none() {
+                    variant.rename = Some(variant.name.clone());
+                }
+                variant.name = runtime.clone();
+            }
+            map_aliases(&mut variant.aliases, &mapping.fields);
+        }
+
+        for method in &mut object.methods {
+            let key = (method.name.clone(), method.parameters.len());
+            if let Some(runtime) = mapping.methods.get(&key) {
+                if method.rename.is_none() {
+                    method.rename = Some(method.name.clone());
+                }
+                method.name = runtime.clone();
+            }
+        }
+
+        true
+    }
+
+    /// Finds the mapping of the object by it's class, or by it's name when the object doesn't
+    /// declare a class and the name is unique among the mapped classes.
+    fn find_class(&self, object: &Object) -> Option<(&String, &ClassMapping)> {
+        if let Some(class_name) = &object.class_name {
+            return self.classes.get_key_value(class_name);
+        }
+
+        let mut candidates = self
+            .classes
+            .iter()
+            .filter(|(readable, _)| simple_name(readable) == object.name);
+
+        match (candidates.next(), candidates.next()) {
+            (Some(found), None) => Some(found),
+            _ => None,
+        }
+    }
+
+    pub fn file_name(&self) -> String {
+        Path::new(&self.path)
+            .file_name()
+            .map(|x| x.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+fn map_aliases(aliases: &mut [String], names: &HashMap<String, String>) {
+    for alias in aliases {
+        if let Some(runtime) = names.get(alias.as_str()) {
+            *alias = runtime.clone();
+        }
+    }
+}
+
+fn simple_name(class: &str) -> &str {
+    class.rsplit(['.', '$']).next().unwrap_or(class)
+}
+
+fn invalid(line: usize, message: &str) -> SourceError {
+    SourceError::Mappings(format!("line {}: {message}", line + 1))
+}
+
+/// Parses the ProGuard format, as used by the Mojang mappings:
+/// ```text
+/// com.example.Foo -> a:
+///     int count -> b
+///     1:4:void tick(float,int) -> c
+/// ```
+fn parse_proguard(source: &str) -> Result<HashMap<String, ClassMapping>, SourceError> {
+    let mut classes = HashMap::new();
+    let mut current: Option<(String, ClassMapping)> = None;
+
+    for (number, line) in source.lines().enumerate() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        let (left, right) = line
+            .split_once(" -> ")
+            .ok_or_else(|| invalid(number, "expected 'name -> obfuscated'"))?;
+
+        if !line.starts_with([' ', '\t']) {
+            let runtime = right
+                .strip_suffix(':')
+                .ok_or_else(|| invalid(number, "expected ':' at the end of the class"))?;
+
+            if let Some((readable, mapping)) = current.take() {
+                classes.insert(readable, mapping);
+            }
+            current = Some((
+                left.trim().to_string(),
+                ClassMapping {
+                    runtime: runtime.trim().to_string(),
+                    ..Default::default()
+                },
+            ));
+            continue;
+        }
+
+        let (_, mapping) = current
+            .as_mut()
+            .ok_or_else(|| invalid(number, "member found outside of a class"))?;
+
+        // Members are '[lines:]type name[(parameters)][:lines]'
+        let (_, member) = left
+            .trim()
+            .rsplit_once(' ')
+            .ok_or_else(|| invalid(number, "expected the type and name of the member"))?;
+        let runtime = right.trim().to_string();
+
+        match member.split_once('(') {
+            Some((name, parameters)) => {
+                let parameters = parameters
+                    .split_once(')')
+                    .ok_or_else(|| invalid(number, "expected ')' after the parameters"))?
+                    .0;
+                let count = parameters.split(',').filter(|x| !x.is_empty()).count();
+                mapping.methods.insert((name.to_string(), count), runtime);
+            }
+            None => {
+                mapping.fields.insert(member.to_string(), runtime);
+            }
+        }
+    }
+
+    if let Some((readable, mapping)) = current {
+        classes.insert(readable, mapping);
+    }
+
+    Ok(classes)
+}
+
+/// Parses the Tiny v2 format, where columns are separated by tabs and members are indented under
+/// their class, e.g. `c a com/example/Foo` followed by `f I b count`.
+/// The readable names are taken from the `named` namespace, or the last one if there is none, and
+/// the runtime names from the `intermediary` namespace, which Fabric runs on, or the first one if
+/// there is none.
+fn parse_tiny(source: &str) -> Result<HashMap<String, ClassMapping>, SourceError> {
+    let mut lines = source.lines().enumerate();
+    let (_, header) = lines.next().expect("The header was checked before parsing");
+
+    let namespaces = header.split('\t').skip(3).collect::<Vec<&str>>();
+    if namespaces.len() < 2 {
+        return Err(invalid(0, "expected at least two namespaces"));
+    }
+    let readable_index = namespaces
+        .iter()
+        .position(|x| *x == "named")
+        .unwrap_or(namespaces.len() - 1);
+    let runtime_index = namespaces
+        .iter()
+        .position(|x| *x == "intermediary")
+        .unwrap_or_default();
+
+    // Names missing in a namespace are the same as in the first one
+    let name_at = |names: &[&str], index: usize| -> String {
+        match names.get(index) {
+            Some(name) if !name.is_empty() => name.replace('/', "."),
+            _ => names[0].replace('/', "."),
+        }
+    };
+
+    let mut classes = HashMap::new();
+    let mut current: Option<(String, ClassMapping)> = None;
+
+    for (number, line) in lines {
+        let columns = line.split('\t').collect::<Vec<&str>>();
+
+        match columns.as_slice() {
+            ["c", names @ ..] if !names.is_empty() => {
+                if let Some((readable, mapping)) = current.take() {
+                    classes.insert(readable, mapping);
+                }
+                current = Some((
+                    name_at(names, readable_index),
+                    ClassMapping {
+                        runtime: name_at(names, runtime_index),
+                        ..Default::default()
+                    },
+                ));
+            }
+            ["", kind @ ("f" | "m"), descriptor, names @ ..] if !names.is_empty() => {
+                let (_, mapping) = current
+                    .as_mut()
+                    .ok_or_else(|| invalid(number, "member found outside of a class"))?;
+
+                let readable = name_at(names, readable_index);
+                let runtime = name_at(names, runtime_index);
+
+                if *kind == "f" {
+                    mapping.fields.insert(readable, runtime);
+                } else {
+                    let count = descriptor_parameters(descriptor)
+                        .ok_or_else(|| invalid(number, "invalid method descriptor"))?;
+                    mapping.methods.insert((readable, count), runtime);
+                }
+            }
+            // Parameters, local variables and comments aren't needed
+            _ => continue,
+        }
+    }
+
+    if let Some((readable, mapping)) = current {
+        classes.insert(readable, mapping);
+    }
+
+    Ok(classes)
+}
+
+/// Counts the parameters of a JVM method descriptor such as `(I[Ljava/lang/String;)V`
+fn descriptor_parameters(descriptor: &str) -> Option<usize> {
+    let parameters = descriptor.strip_prefix('(')?.split_once(')')?.0;
+    let mut chars = parameters.chars();
+    let mut count = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => continue,
+            'L' => {
+                chars.find(|x| *x == ';')?;
+                count += 1;
+            }
+            _ => count += 1,
+        }
+    }
+
+    Some(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proguard_members() {
+        let classes = parse_proguard(
+            "# comment\n\
+             com.example.Foo -> a:\n\
+             \x20   int count -> b\n\
+             \x20   java.lang.String[] names -> c\n\
+             \x20   1:4:void tick(float,int) -> d\n\
+             \x20   5:5:void reset():10:10 -> e\n",
+        )
+        .unwrap();
+
+        let foo = &classes["com.example.Foo"];
+        assert_eq!(foo.runtime, "a");
+        assert_eq!(foo.fields["count"], "b");
+        assert_eq!(foo.fields["names"], "c");
+        assert_eq!(foo.methods[&("tick".to_string(), 2)], "d");
+        assert_eq!(foo.methods[&("reset".to_string(), 0)], "e");
+    }
+
+    #[test]
+    fn proguard_malformed_line() {
+        let error = parse_proguard("com.example.Foo -> a:\n    int count b\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not parse the mappings file: line 2: expected 'name -> obfuscated'"
+        );
+        assert!(parse_proguard("    int count -> b\n").is_err());
+    }
+
+    #[test]
+    fn tiny_namespaces() {
+        let classes = parse_tiny(
+            "tiny\t2\t0\tofficial\tintermediary\tnamed\n\
+             c\ta\tclass_1\tcom/example/Foo\n\
+             \tf\tI\tb\tfield_1\tcount\n\
+             \tm\t(I[Ljava/lang/String;)V\tc\tmethod_1\ttick\n\
+             \t\tp\t1\t\t\tvalue\n\
+             c\tb\tclass_2\t\n\
+             \tf\tJ\td\tfield_2\t\n",
+        )
+        .unwrap();
+
+        let foo = &classes["com.example.Foo"];
+        assert_eq!(foo.runtime, "class_1");
+        assert_eq!(foo.fields["count"], "field_1");
+        assert_eq!(foo.methods[&("tick".to_string(), 2)], "method_1");
+
+        // Empty columns fall back to the first namespace
+        let unnamed = &classes["b"];
+        assert_eq!(unnamed.runtime, "class_2");
+        assert_eq!(unnamed.fields["d"], "field_2");
+    }
+
+    #[test]
+    fn tiny_without_intermediary() {
+        let classes = parse_tiny(
+            "tiny\t2\t0\tofficial\tnamed\n\
+             c\ta\tcom/example/Foo\n\
+             \tf\tI\tb\tcount\n",
+        )
+        .unwrap();
+
+        let foo = &classes["com.example.Foo"];
+        assert_eq!(foo.runtime, "a");
+        assert_eq!(foo.fields["count"], "b");
+    }
+
+    #[test]
+    fn tiny_malformed_line() {
+        assert!(parse_tiny("tiny\t2\t0\tofficial\n").is_err());
+        assert!(parse_tiny("tiny\t2\t0\tofficial\tnamed\n\tf\tI\tb\tcount\n").is_err());
+        assert!(parse_tiny("tiny\t2\t0\tofficial\tnamed\nc\ta\tFoo\n\tm\t(L\tb\ttick\n").is_err());
+    }
+
+    #[test]
+    fn descriptors() {
+        assert_eq!(descriptor_parameters("()V"), Some(0));
+        assert_eq!(descriptor_parameters("(IJ)V"), Some(2));
+        assert_eq!(descriptor_parameters("([I[[Ljava/lang/String;Z)V"), Some(3));
+        assert_eq!(descriptor_parameters("(Ljava/lang/String)V"), None);
+        assert_eq!(descriptor_parameters("IJ"), None);
+    }
+}
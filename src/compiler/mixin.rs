@@ -0,0 +1,194 @@
+use crate::{java, Object};
+
+use super::{capitalize, JavaSource, SourceError};
+
+/// Name of the mixin config, written in the root of the output directory
+pub const CONFIG_FILE: &str = "accessors.mixins.json";
+
+// Static accessors and invokers need a body, it's replaced by the mixin when applied
+const STATIC_BODY: &str = r#"
+throw new AssertionError();
+"#;
+
+const CONFIG: &str = r#"{
+  "required": true,
+  "minVersion": "0.8",
+  "package": "{{PACKAGE}}",
+  "compatibilityLevel": "JAVA_8",
+  "mixins": [
+{{MIXINS}}
+  ]
+}
+"#;
+
+/// Mixin interface of the object, with an `@Accessor` getter and setter for every field and an
+/// `@Invoker` for every method.
+/// Mixins target a single name, so fields with aliases or from the hierarchy are rejected, as
+/// well as optional ones, since every member has to exist when the mixin is applied.
+pub fn generate(
+    object: &Object,
+    includes: &[Object],
+    comment: String,
+) -> Result<String, SourceError> {
+    let targets = object
+        .classes()
+        .map(|x| format!("\"{x}\""))
+        .collect::<Vec<String>>();
+    if targets.is_empty() {
+        return Err(SourceError::MissingMixinTarget(
+            object.display_name().to_string(),
+        ));
+    }
+
+    for field in &object.fields {
+        let unsupported = [
+            (field.hierarchy, "hierarchy"),
+            (field.stop_at.is_some(), "stop_at"),
+            (!field.aliases.is_empty(), "aliases"),
+            (field.optional, "optional"),
+        ];
+        if let Some((_, feature)) = unsupported.into_iter().find(|(used, _)| *used) {
+            return Err(SourceError::UnsupportedMixinField(
+                field.name.clone(),
+                feature,
+            ));
+        }
+    }
+
+    let original_name = |field_type: &crate::Type| {
+        field_type
+            .original_java_name(object, includes)
+            .map_err(SourceError::MissingMixinTarget)
+    };
+
+    let mut methods = vec![];
+
+    for field in &object.fields {
+        let type_name = original_name(&field.field_type)?;
+        let name = capitalize(field.display_name());
+
+        methods.push(
+            member()
+                .annotation(format!("@Accessor(\"{}\")", field.name))
+                .name(format!("get{name}"))
+                .return_type(type_name.clone())
+                .is_static(field.is_static)
+                .call(),
+        );
+        methods.push(
+            member()
+                .annotation(format!("@Accessor(\"{}\")", field.name))
+                .name(format!("set{name}"))
+                .arguments(vec![java::Argument::builder()
+                    .name("value")
+                    .type_name(type_name)
+                    .build()])
+                .is_static(field.is_static)
+                .call(),
+        );
+    }
+
+    for method in &object.methods {
+        let arguments = method
+            .parameters
+            .iter()
+            .enumerate()
+            .map(|(i, parameter)| {
+                Ok(java::Argument::builder()
+                    .name(format!("arg{i}"))
+                    .type_name(original_name(parameter)?)
+                    .build())
+            })
+            .collect::<Result<Vec<java::Argument>, SourceError>>()?;
+
+        methods.push(
+            member()
+                .annotation(format!("@Invoker(\"{}\")", method.name))
+                .name(format!("invoke{}", capitalize(method.display_name())))
+                .arguments(arguments)
+                .maybe_return_type(method.return_type.as_ref().map(original_name).transpose()?)
+                .is_static(method.is_static)
+                .call(),
+        );
+    }
+
+    Ok(java::Class::builder()
+        .name(format!("{}Accessor", object.display_name()))
+        .class_type(java::ClassType::Interface)
+        .package(&object.package)
+        .comment(comment)
+        .includes(vec![
+            "org.spongepowered.asm.mixin.Mixin".to_string(),
+            "org.spongepowered.asm.mixin.gen.Accessor".to_string(),
+            "org.spongepowered.asm.mixin.gen.Invoker".to_string(),
+        ])
+        .annotations(vec![format!(
+            "@Mixin(targets = {{ {} }})",
+            targets.join(", ")
+        )])
+        .methods(methods)
+        .build()
+        .source())
+}
+
+/// Mixin config listing every generated mixin, relative to the package they all share.
+pub fn config(sources: &[JavaSource]) -> Result<String, SourceError> {
+    let mut classes = sources
+        .iter()
+        .map(|x| x.class_name.as_str())
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<&str>>();
+    classes.sort();
+
+    // Longest package every mixin is in
+    let mut package = classes.first().map(|x| package_of(x)).unwrap_or_default();
+    for class in &classes {
+        while !package.is_empty()
+            && package_of(class) != package
+            && !package_of(class).starts_with(&format!("{package}."))
+        {
+            package = package.rsplit_once('.').map(|x| x.0).unwrap_or_default();
+        }
+
+        if package.is_empty() {
+            return Err(SourceError::MixinPackage(
+                package_of(classes[0]).to_string(),
+                package_of(class).to_string(),
+            ));
+        }
+    }
+
+    let mixins = classes
+        .iter()
+        .map(|x| format!("    \"{}\"", &x[package.len() + 1..]))
+        .collect::<Vec<String>>()
+        .join(",\n");
+
+    Ok(CONFIG
+        .replace("{{PACKAGE}}", package)
+        .replace("{{MIXINS}}", &mixins))
+}
+
+fn package_of(class: &str) -> &str {
+    class.rsplit_once('.').map(|x| x.0).unwrap_or_default()
+}
+
+/// Accessor or invoker, abstract unless static
+#[bon::builder]
+fn member(
+    #[builder(into)] annotation: String,
+    #[builder(into)] name: String,
+    #[builder(default)] arguments: Vec<java::Argument>,
+    return_type: Option<String>,
+    is_static: bool,
+) -> java::Method {
+    java::Method::builder()
+        .annotations(vec![annotation])
+        .name(name)
+        .arguments(arguments)
+        .maybe_return_type(return_type)
+        .is_static(is_static)
+        .is_abstract(!is_static)
+        .body(if is_static { STATIC_BODY } else { "" })
+        .build()
+}
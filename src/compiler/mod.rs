@@ -4,36 +4,188 @@ use bon::builder;
 use ron::de::SpannedError;
 use thiserror::Error;
 
-use crate::{java, Object};
+use crate::{java, Backend, FieldAccess, Lang, Mode, Object};
 
 mod constructs;
+mod jni;
+mod kotlin;
+mod mappings;
+mod mixin;
+mod span;
+mod symbols;
+mod validation;
+
+pub use mappings::Mappings;
+pub use span::Span;
+use symbols::Symbols;
 
 const ALLOWED_EXTENSIONS: &[&str] = &["ron"];
 
 #[derive(Debug)]
 pub struct JavaSource {
     pub path: PathBuf,
+    /// Fully qualified name of the generated type, empty for other files
+    pub class_name: String,
     pub content: String,
 }
 
 #[derive(Error, Debug)]
 pub enum SourceError {
-    #[error("An IO error ocurred while reading a source file or directory: {0}")]
+    #[error("An IO error ocurred while reading or writing a file or directory: {0}")]
     IO(std::io::Error),
 
     #[error("An error ocurred while deserializing the file: {0}")]
     Deserialization(SpannedError),
 
-    #[error("Object had both fields and variants, the type between enum or class could not be determined.")]
+    #[error("An error ocurred while serializing the file: {0}")]
+    Serialization(ron::Error),
+
+    #[error("Object had both fields or methods and variants, the type between enum or class could not be determined.")]
     AmbiguousClassOrEnum,
+
+    #[error("Variant name or alias '{0}' is used more than once.")]
+    DuplicateVariant(String),
+
+    #[error("Static member '{0}' needs the object to declare it's original class.")]
+    MissingClass(String),
+
+    #[error("Could not parse the mappings file: {0}")]
+    Mappings(String),
+
+    #[error("Object '{0}' needs to declare it's original class to be used in a mixin.")]
+    MissingMixinTarget(String),
+
+    #[error("Mixins in packages '{0}' and '{1}' don't share a package for the mixin config.")]
+    MixinPackage(String, String),
+
+    #[error("Field '{0}' uses '{1}', which isn't supported in mixins.")]
+    UnsupportedMixinField(String, &'static str),
+
+    #[error("Object '{0}' needs to declare it's original class to be used from JNI.")]
+    MissingJniClass(String),
+
+    #[error("Field '{0}' has an ancestor to stop at, but doesn't search the hierarchy.")]
+    StopWithoutHierarchy(String),
+
+    #[error("Could not find the extended object '{0}' in the project or the includes.")]
+    MissingParent(String),
+
+    #[error("Object can't extend '{0}', only classes can extend and be extended.")]
+    InvalidParent(String),
+
+    #[error("Type '{0}' could be any of {1}, it must be qualified with the package.")]
+    AmbiguousType(String, String),
+
+    #[error("Type '{0}' does not match any schema of the project or the includes.")]
+    UndefinedType(String),
+
+    #[error("Accessor '{0}' is also generated from '{1}'.")]
+    DuplicateAccessor(String, String),
+
+    #[error("Field name '{0}' is used more than once.")]
+    DuplicateField(String),
+
+    #[error("Field name '{0}' is already used by the extended object '{1}'.")]
+    InheritedField(String, String),
+
+    #[error("Includes form a cycle: {0}.")]
+    IncludeCycle(String),
+
+    #[error("Objects extend each other in a cycle: {0}.")]
+    InheritanceCycle(String),
+
+    // Warnings, the files are still generated
+    #[error("No mapping found for '{0}' in '{1}'.")]
+    UnmappedClass(String, String),
+
+    #[error("Only accessors can be generated in Kotlin, ignoring the Kotlin language.")]
+    IgnoredLang,
+
+    #[error("Only Java accessors can use handles, ignoring the handles field access.")]
+    IgnoredFieldAccess,
 }
 
 /// Target: the target directory where the original source files are.
-/// Will iterate recursively on it and compile everything.
+/// Will iterate recursively on it and compile everything, every schema is parsed once beforehand
+/// so objects can reference each other by name.
+/// Mappings: when present, names are rewritten into their runtime names.
+/// Mode: used by the objects that don't select their own.
+/// Field access: how every generated accessor reaches the fields.
+/// Backend: what is generated, mixins get a config listing them in the output directory and JNI
+/// modules get a `mod.rs` in every directory.
+/// Lang: language of the generated accessors, mixins are always Java and JNI modules Rust.
+/// Warnings: filled with what doesn't stop the compilation, but is likely not intended.
 #[builder]
 pub fn compile(
     target: impl Into<PathBuf>,
     output: Option<impl Into<PathBuf>>,
+    mappings: Option<&Mappings>,
+    #[builder(default)] mode: Mode,
+    #[builder(default)] field_access: FieldAccess,
+    #[builder(default)] backend: Backend,
+    #[builder(default)] lang: Lang,
+    warnings: &mut Vec<(PathBuf, SourceError)>,
+) -> Result<Vec<JavaSource>, Vec<(PathBuf, SourceError)>> {
+    let output: Option<PathBuf> = output.map(Into::into);
+    let target = target.into();
+
+    if lang == Lang::Kotlin && backend != Backend::Accessor {
+        warnings.push((target.clone(), SourceError::IgnoredLang));
+    }
+    if field_access == FieldAccess::Handles
+        && (lang == Lang::Kotlin || backend != Backend::Accessor)
+    {
+        warnings.push((target.clone(), SourceError::IgnoredFieldAccess));
+    }
+
+    // Nothing is compiled unless every schema can be parsed and is valid
+    let (symbols, mut failed) = Symbols::index(&target, mappings, warnings);
+    failed.extend(validation::validate(&symbols, mappings, warnings));
+    if !failed.is_empty() {
+        return Err(failed);
+    }
+
+    let mut completed = compile_directory()
+        .target(target)
+        .maybe_output(output.clone())
+        .symbols(&symbols)
+        .maybe_mappings(mappings)
+        .mode(mode)
+        .field_access(field_access)
+        .backend(backend)
+        .lang(lang)
+        .call()?;
+
+    if backend == Backend::Mixin {
+        let path = output.clone().unwrap_or_default().join(mixin::CONFIG_FILE);
+        match mixin::config(&completed) {
+            Ok(content) => completed.push(JavaSource {
+                path,
+                class_name: String::new(),
+                content,
+            }),
+            Err(err) => return Err(vec![(path, err)]),
+        }
+    }
+
+    if backend == Backend::Jni {
+        let modules = jni::modules(&completed, &output.unwrap_or_default());
+        completed.extend(modules);
+    }
+
+    Ok(completed)
+}
+
+#[builder]
+fn compile_directory(
+    target: impl Into<PathBuf>,
+    output: Option<impl Into<PathBuf>>,
+    symbols: &Symbols,
+    mappings: Option<&Mappings>,
+    mode: Mode,
+    field_access: FieldAccess,
+    backend: Backend,
+    lang: Lang,
 ) -> Result<Vec<JavaSource>, Vec<(PathBuf, SourceError)>> {
     let target = target.into();
     let output: Option<PathBuf> = output.map(Into::into);
@@ -63,7 +215,17 @@ pub fn compile(
             let dir_name = path.file_name().expect("dir_name");
             output.push(dir_name);
 
-            match compile().target(&path).output(output).call() {
+            match compile_directory()
+                .target(&path)
+                .output(output)
+                .symbols(symbols)
+                .maybe_mappings(mappings)
+                .mode(mode)
+                .field_access(field_access)
+                .backend(backend)
+                .lang(lang)
+                .call()
+            {
                 Ok(mut x) => completed.append(&mut x),
                 Err(mut x) => failed.append(&mut x),
             }
@@ -90,9 +252,15 @@ pub fn compile(
         match compile_file()
             .target(&path)
             .maybe_output(output.as_ref())
+            .symbols(symbols)
+            .maybe_mappings(mappings)
+            .mode(mode)
+            .field_access(field_access)
+            .backend(backend)
+            .lang(lang)
             .call()
         {
-            Ok(x) => completed.push(x),
+            Ok(x) => completed.extend(x),
             Err(x) => failed.push((path, x)),
         }
     }
@@ -107,7 +275,13 @@ pub fn compile(
 fn compile_file(
     target: impl Into<PathBuf>,
     output: Option<impl Into<PathBuf>>,
-) -> Result<JavaSource, SourceError> {
+    symbols: &Symbols,
+    mappings: Option<&Mappings>,
+    mode: Mode,
+    field_access: FieldAccess,
+    backend: Backend,
+    lang: Lang,
+) -> Result<Option<JavaSource>, SourceError> {
     let target = target.into();
     let target_parent = target.parent().expect("File to have a parent");
 
@@ -117,54 +291,183 @@ fn compile_file(
         .to_str()
         .expect("File Name to be valid UTF8");
 
-    let source = std::fs::read_to_string(&target).map_err(SourceError::IO)?;
     let output = output.map(Into::into).unwrap_or_default();
 
-    let current: Object = ron::from_str(&source).map_err(SourceError::Deserialization)?;
+    // Schemas that failed to parse were already reported while indexing
+    let Some(current) = symbols.get(&target) else {
+        return Ok(None);
+    };
+    let mut current = current.clone();
+    current.mode.get_or_insert(mode);
+    current.field_access = field_access;
 
-    // If object has both fields and variants so it's type between enum and class could not be
+    // If object has both fields or methods and variants so it's type between enum and class could not be
     // determined.
-    if !current.fields.is_empty() && !current.variants.is_empty() {
+    if (!current.fields.is_empty() || !current.methods.is_empty()) && !current.variants.is_empty() {
         return Err(SourceError::AmbiguousClassOrEnum);
     }
 
-    let is_enum = !current.variants.is_empty();
-
-    let output = output.join(format!("{}Accessor.java", current.display_name()));
+    // Every name and alias must map to a single variant, otherwise the generated switch would
+    // have duplicated case labels.
+    let mut variant_names = std::collections::HashSet::new();
+    for name in current
+        .variants
+        .iter()
+        .flat_map(|x| std::iter::once(&x.name).chain(&x.aliases))
+    {
+        if !variant_names.insert(name) {
+            return Err(SourceError::DuplicateVariant(name.clone()));
+        }
+    }
 
-    let includes: Vec<Object> = current
-        .includes
+    if let Some(field) = current
+        .fields
         .iter()
-        .map(|x| resolve_include(target_parent, x))
-        .collect::<Result<Vec<Object>, SourceError>>()?;
+        .find(|x| x.stop_at.is_some() && !x.hierarchy)
+    {
+        return Err(SourceError::StopWithoutHierarchy(field.name.clone()));
+    }
+
+    // Static members are reached through the original class instead of an instance
+    let static_member = current
+        .static_fields()
+        .map(|x| &x.name)
+        .chain(
+            current
+                .methods
+                .iter()
+                .filter(|x| x.is_static)
+                .map(|x| &x.name),
+        )
+        .next();
+    if let (Some(member), None) = (static_member, current.classes().next()) {
+        return Err(SourceError::MissingClass(member.clone()));
+    }
+
+    let is_enum = current.is_enum();
+
+    let output = output.join(match (backend, lang) {
+        (Backend::Jni, _) => format!("{}.rs", jni::module_name(current.display_name())),
+        (Backend::Accessor, Lang::Kotlin) => format!("{}Accessor.kt", current.display_name()),
+        _ => format!("{}Accessor.java", current.display_name()),
+    });
+
+    let includes = object_includes(&current, Some(target_parent), symbols, mappings)?;
+
+    if let Some(parent) = &current.extends {
+        match includes.iter().find(|x| x.is_named(parent)) {
+            Some(object) if !object.is_enum() && !is_enum => {}
+            Some(_) => return Err(SourceError::InvalidParent(parent.clone())),
+            None => return Err(SourceError::MissingParent(parent.clone())),
+        }
+    }
+
+    let mappings_comment = match mappings {
+        Some(mappings) => format!("\nMappings: {}", mappings.file_name()),
+        None => String::new(),
+    };
+
+    let class_name = format!("{}.{}Accessor", current.package, current.display_name());
+
+    if backend == Backend::Mixin {
+        // The original enums are used as they are, there is nothing to access
+        if is_enum {
+            tracing::debug!("Skipping enum '{}' for mixins", current.display_name());
+            return Ok(None);
+        }
+
+        return Ok(Some(JavaSource {
+            path: output,
+            class_name,
+            content: mixin::generate(
+                &current,
+                &includes,
+                format!(
+                    "Mixin autogenerated from {}. DO NOT EDIT\nOriginal name: {}{}",
+                    file_name, current.name, mappings_comment
+                ),
+            )?,
+        }));
+    }
+
+    if backend == Backend::Jni {
+        // The original enums are only passed around as objects
+        if is_enum {
+            tracing::debug!("Skipping enum '{}' for JNI", current.display_name());
+            return Ok(None);
+        }
+
+        // Modules don't extend each other, the fields of the parents are accessed directly
+        let inherited = ancestors(&current, &includes, symbols, mappings)?;
+
+        return Ok(Some(JavaSource {
+            path: output,
+            class_name,
+            content: jni::generate(
+                &current,
+                &includes,
+                &inherited,
+                format!(
+                    "Module autogenerated from {}. DO NOT EDIT\nOriginal name: {}{}",
+                    file_name, current.name, mappings_comment
+                ),
+            )?,
+        }));
+    }
+
+    if lang == Lang::Kotlin {
+        let comment = format!(
+            "{} autogenerated from {}. DO NOT EDIT\nOriginal name: {}{}",
+            if is_enum { "Enum" } else { "Class" },
+            file_name,
+            current.name,
+            mappings_comment
+        );
+
+        return Ok(Some(JavaSource {
+            path: output,
+            class_name,
+            content: if is_enum {
+                kotlin::generate_enum(&current, &includes, comment)
+            } else {
+                kotlin::generate_class(&current, &includes, comment)
+            },
+        }));
+    }
 
     let source = if is_enum {
         java::Enum::builder()
             .name(format!("{}Accessor", current.display_name()))
             .package(&current.package)
             .comment(format!(
-                "Enum autogenerated from {}. DO NOT EDIT.\nOriginal name: {}",
-                file_name, current.name
+                "Enum autogenerated from {}. DO NOT EDIT.\nOriginal name: {}{}",
+                file_name, current.name, mappings_comment
             ))
-            .includes(
-                includes
-                    .iter()
-                    .map(|x| format!("{}.{}Accessor", &x.package, x.display_name()))
-                    .collect(),
-            )
+            .includes(imports(&current, &includes))
             .variants(
                 current
                     .variants
                     .iter()
-                    .map(|x| x.display_name().to_owned())
+                    .map(|x| {
+                        let names = std::iter::once(&x.name)
+                            .chain(&x.aliases)
+                            .map(|name| format!("\"{name}\""))
+                            .collect::<Vec<String>>()
+                            .join(", ");
+                        format!("{}({names})", x.display_name())
+                    })
                     .collect(),
             )
+            .fields(constructs::enum_to_original::fields())
             .methods(
                 vec![
+                    constructs::enum_constructor::generate(&current),
                     constructs::single_enum_accessor::generate(&current),
                     constructs::array_accessor::generate(&current),
                 ]
                 .into_iter()
+                .chain(constructs::enum_to_original::generate(&current))
+                .chain(constructs::target_class::generate(&current))
                 .collect(),
             )
             .build()
@@ -174,63 +477,243 @@ fn compile_file(
             .name(format!("{}Accessor", current.display_name()))
             .package(&current.package)
             .comment(format!(
-                "Class autogenerated from {}. DO NOT EDIT\nOriginal name: {}",
-                file_name, current.name
+                "Class autogenerated from {}. DO NOT EDIT\nOriginal name: {}{}",
+                file_name, current.name, mappings_comment
             ))
-            .includes(
-                includes
-                    .iter()
-                    .map(|x| format!("{}.{}Accessor", &x.package, x.display_name()))
-                    .collect(),
+            .includes(imports(&current, &includes))
+            .maybe_extends(
+                current
+                    .extends
+                    .as_ref()
+                    .map(|parent| format!("{parent}Accessor")),
             )
             .fields(
-                vec![java::Field::builder()
-                    .name("self")
-                    .type_name("Object")
-                    .visibility(java::Visibility::Private)
-                    .build()]
-                .into_iter()
-                .chain(current.fields.iter().map(|x| {
-                    java::Field::builder()
-                        .name(x.display_name())
-                        .type_name(x.field_type.java_name(&current))
-                        .visibility(java::Visibility::Public)
-                        .maybe_comment(if x.rename.is_some() {
-                            Some(format!("Original name: {}", x.name))
-                        } else {
-                            None
-                        })
-                        .build()
-                }))
-                .collect(),
+                // Accessors extending this one use the inherited object
+                current
+                    .extends
+                    .is_none()
+                    .then(|| {
+                        java::Field::builder()
+                            .name("self")
+                            .type_name("Object")
+                            .visibility(java::Visibility::Protected)
+                            .build()
+                    })
+                    .into_iter()
+                    .chain(
+                        current
+                            .instance_fields()
+                            .filter(|_| !current.is_live())
+                            .map(|x| {
+                                java::Field::builder()
+                                    .name(x.display_name())
+                                    .type_name(x.field_type.java_name(&current))
+                                    .visibility(java::Visibility::Public)
+                                    .maybe_comment(if x.rename.is_some() {
+                                        Some(format!("Original name: {}", x.name))
+                                    } else {
+                                        None
+                                    })
+                                    .build()
+                            }),
+                    )
+                    .chain(constructs::field_presence::fields(&current))
+                    .chain(if current.fields.is_empty() {
+                        vec![]
+                    } else {
+                        constructs::find_field::fields(current.field_access)
+                    })
+                    .chain(if current.methods.is_empty() {
+                        vec![]
+                    } else {
+                        constructs::find_method::fields()
+                    })
+                    .collect(),
             )
             .methods(
                 vec![
                     constructs::class_constructor::generate(&current),
-                    constructs::single_class_accessor::generate(&current),
+                    constructs::class_unwrap::generate(),
+                ]
+                .into_iter()
+                .chain(constructs::single_class_accessor::generate(&current))
+                .chain([
                     constructs::array_accessor::generate(&current),
-                    constructs::nested_array_accessor::generate(),
                     constructs::clear_inner_refs::generate(&current, &includes),
                     constructs::clear_inner_refs_array::generate(),
-                ]
-                .into_iter()
+                ])
+                .chain(constructs::collection_accessor::generate(&current))
+                .chain(constructs::collection_unwrapper::generate(
+                    &current, &includes,
+                ))
+                .chain(constructs::field_getters::generate(&current))
                 .chain(constructs::field_setters::generate(
                     &current.fields,
                     &current,
+                    &includes,
                 ))
+                .chain(constructs::static_field_accessors::generate(
+                    &current, &includes,
+                ))
+                .chain(constructs::field_presence::generate(&current))
+                .chain(constructs::method_invokers::generate(&current, &includes))
+                .chain(if current.fields.is_empty() {
+                    vec![]
+                } else {
+                    constructs::find_field::generate(current.field_access)
+                })
+                .chain(if current.methods.is_empty() {
+                    None
+                } else {
+                    Some(constructs::find_method::generate())
+                })
+                .chain(constructs::target_class::generate(&current))
                 .collect(),
             )
             .build()
             .source()
     };
 
-    Ok(JavaSource {
+    Ok(Some(JavaSource {
         path: output,
+        class_name,
         content: source,
-    })
+    }))
+}
+
+/// Accessors imported by the accessor of `object`. Names declared by several objects are left
+/// out, those are referenced with their qualified name instead.
+fn imports(object: &Object, includes: &[Object]) -> Vec<String> {
+    includes
+        .iter()
+        .filter(|x| {
+            x.display_name() != object.display_name()
+                && includes
+                    .iter()
+                    .filter(|y| y.display_name() == x.display_name())
+                    .count()
+                    == 1
+        })
+        .map(|x| format!("{}.{}Accessor", &x.package, x.display_name()))
+        .collect()
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Objects referenced by `object`, by it's types and it's parent. Explicit includes override the
+/// objects found by name in the project.
+/// * `working_directory` - Directory of the schema of `object`, None when it's not known and only
+///   the project is searched
+fn object_includes(
+    object: &Object,
+    working_directory: Option<&Path>,
+    symbols: &Symbols,
+    mappings: Option<&Mappings>,
+) -> Result<Vec<Object>, SourceError> {
+    let mut includes = match working_directory {
+        Some(working_directory) => explicit_includes(object, working_directory, symbols, mappings)?,
+        None => vec![],
+    };
+
+    let references = object
+        .types()
+        .flat_map(|x| x.objects(object))
+        .chain(object.extends.as_deref())
+        .collect::<Vec<&str>>();
+    for name in references {
+        if includes.iter().any(|x| x.is_named(name)) {
+            continue;
+        }
+        if let Some(found) = symbols.resolve(name, object)? {
+            // The object itself is not imported
+            if found.qualified_name() != object.qualified_name() {
+                includes.push(found.clone());
+            }
+        }
+    }
+
+    Ok(includes)
+}
+
+/// Objects extended by `object`, from it's parent up, each with the objects it references
+/// * `includes` - Objects referenced by `object`, where it's parent is
+fn ancestors(
+    object: &Object,
+    includes: &[Object],
+    symbols: &Symbols,
+    mappings: Option<&Mappings>,
+) -> Result<Vec<(Object, Vec<Object>)>, SourceError> {
+    let mut ancestors: Vec<(Object, Vec<Object>)> = vec![];
+    let mut next = object
+        .extends
+        .as_ref()
+        .and_then(|parent| includes.iter().find(|x| x.is_named(parent)))
+        .cloned();
+
+    while let Some(parent) = next {
+        // Cycles are rejected by the validation, this only guards against looping forever
+        let visited = parent.qualified_name() == object.qualified_name()
+            || ancestors
+                .iter()
+                .any(|(x, _)| x.qualified_name() == parent.qualified_name());
+        if visited {
+            break;
+        }
+
+        // Parents from outside of the project only have the project to resolve their types
+        let working_directory = symbols
+            .schemas
+            .iter()
+            .find(|x| x.object.qualified_name() == parent.qualified_name())
+            .and_then(|x| x.path.parent());
+        let parent_includes = object_includes(&parent, working_directory, symbols, mappings)?;
+
+        next = parent
+            .extends
+            .as_ref()
+            .and_then(|grandparent| parent_includes.iter().find(|x| x.is_named(grandparent)))
+            .cloned();
+        ancestors.push((parent, parent_includes));
+    }
+
+    Ok(ancestors)
+}
+
+/// Objects of the schemas in the `includes` of `object`, with the mappings applied
+/// * `working_directory` - Directory of the schema of `object`
+fn explicit_includes(
+    object: &Object,
+    working_directory: &Path,
+    symbols: &Symbols,
+    mappings: Option<&Mappings>,
+) -> Result<Vec<Object>, SourceError> {
+    object
+        .includes
+        .iter()
+        .map(
+            |include| match symbols.get(&working_directory.join(include)) {
+                Some(object) => Ok(object.clone()),
+                None => {
+                    let mut object = resolve_include(working_directory, include)?;
+                    // Only the schemas of the project are reported when unmapped, includes
+                    // from outside of it are often of libraries without mappings
+                    if let Some(mappings) = mappings {
+                        mappings.apply(&mut object);
+                    }
+                    Ok(object)
+                }
+            },
+        )
+        .collect()
 }
 
-/// Turns a path include from a src file into a formatted java include
+/// Parses an included schema that is not part of the project
 /// * `working_directory` -  Directory of the current file that is trying to include the other file
 /// * `path` - include path as is on the source file
 fn resolve_include(
@@ -0,0 +1,299 @@
+use serde::Serialize;
+
+use super::SourceError;
+
+/// Location of an error in the source of a schema
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Span {
+    /// Starting at 1
+    pub line: usize,
+    /// Starting at 1, counted in characters
+    pub column: usize,
+    /// Characters covered by the span, at least 1
+    pub length: usize,
+}
+
+impl Span {
+    /// Span of the character offset `start` of `source`, covering `length` characters
+    fn at(source: &str, start: usize, length: usize) -> Self {
+        let before = &source[..start];
+        let line_start = before.rfind('\n').map(|x| x + 1).unwrap_or_default();
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            length: source[start..start + length].chars().count().max(1),
+        }
+    }
+}
+
+/// Where to look for the cause of an error in the source
+enum Needle<'a> {
+    /// First name between quotes given to any of the keys, like `name: "x"` or
+    /// `rename: Some("x")`
+    Value(&'static [&'static str], &'a str),
+    /// Last name given to any of the keys, for the repeated one of duplicates
+    LastValue(&'static [&'static str], &'a str),
+    /// Name of an object used as a type, in `Object("x")`, or as the parent in `extends`
+    Reference(&'a str),
+    /// Last appearance of a name, between quotes, for variants repeated in their aliases
+    Last(&'a str),
+    /// A key of the object, like `extends`
+    Key(&'static str),
+}
+
+/// Keys naming a field, or a method
+const MEMBER_NAMES: &[&str] = &["name", "rename"];
+
+impl SourceError {
+    /// Span of the source the error is about, if it can be found in it.
+    /// Deserialization errors have their position, the rest are located by the names they
+    /// carry, since the schemas are deserialized without positions.
+    pub fn span(&self, source: &str) -> Option<Span> {
+        let needle = match self {
+            SourceError::Deserialization(err) => {
+                return Some(Span {
+                    line: err.position.line,
+                    column: err.position.col,
+                    length: 1,
+                })
+            }
+            SourceError::IO(_)
+            | SourceError::Serialization(_)
+            | SourceError::Mappings(_)
+            | SourceError::MixinPackage(..)
+            | SourceError::IgnoredLang
+            | SourceError::IgnoredFieldAccess => return None,
+            SourceError::UnmappedClass(name, _) => Needle::Value(&["name"], name),
+            SourceError::AmbiguousClassOrEnum => Needle::Key("variants"),
+            SourceError::DuplicateVariant(name) => Needle::Last(name),
+            SourceError::DuplicateField(name) => Needle::LastValue(MEMBER_NAMES, name),
+            SourceError::MissingClass(name)
+            | SourceError::StopWithoutHierarchy(name)
+            | SourceError::InheritedField(name, _)
+            | SourceError::UnsupportedMixinField(name, _) => Needle::Value(MEMBER_NAMES, name),
+            SourceError::AmbiguousType(name, _) | SourceError::UndefinedType(name) => {
+                Needle::Reference(name)
+            }
+            SourceError::MissingMixinTarget(_)
+            | SourceError::MissingJniClass(_)
+            | SourceError::DuplicateAccessor(..) => Needle::Key("name"),
+            SourceError::MissingParent(name) | SourceError::InvalidParent(name) => {
+                Needle::Value(&["extends"], name)
+            }
+            SourceError::IncludeCycle(_) => Needle::Key("includes"),
+            SourceError::InheritanceCycle(_) => Needle::Key("extends"),
+        };
+
+        let (start, length) = match needle {
+            Needle::Value(keys, name) => {
+                let quoted = format!("\"{name}\"");
+                (key_values(source, keys, &quoted).min()?, quoted.len())
+            }
+            Needle::LastValue(keys, name) => {
+                let quoted = format!("\"{name}\"");
+                (key_values(source, keys, &quoted).max()?, quoted.len())
+            }
+            Needle::Reference(name) => {
+                let quoted = format!("\"{name}\"");
+                let object = source
+                    .match_indices("Object")
+                    .filter(|(x, _)| !is_identifier(source[..*x].chars().next_back()))
+                    .find_map(|(x, _)| value_after(source, x + "Object".len(), '(', &quoted));
+                let parent = || key_values(source, &["extends"], &quoted).min();
+                (object.or_else(parent)?, quoted.len())
+            }
+            Needle::Last(name) => {
+                let quoted = format!("\"{name}\"");
+                (source.rfind(&quoted)?, quoted.len())
+            }
+            Needle::Key(key) => (find_key(source, key)?, key.len()),
+        };
+
+        Some(Span::at(source, start, length))
+    }
+
+    /// Stable identifier of the kind of error, for tools reading the diagnostics
+    pub fn code(&self) -> &'static str {
+        match self {
+            SourceError::IO(_) => "io",
+            SourceError::Deserialization(_) => "deserialization",
+            SourceError::Serialization(_) => "serialization",
+            SourceError::AmbiguousClassOrEnum => "ambiguous-class-or-enum",
+            SourceError::DuplicateVariant(_) => "duplicate-variant",
+            SourceError::MissingClass(_) => "missing-class",
+            SourceError::Mappings(_) => "mappings",
+            SourceError::MissingMixinTarget(_) => "missing-mixin-target",
+            SourceError::MixinPackage(..) => "mixin-package",
+            SourceError::UnsupportedMixinField(..) => "unsupported-mixin-field",
+            SourceError::MissingJniClass(_) => "missing-jni-class",
+            SourceError::StopWithoutHierarchy(_) => "stop-without-hierarchy",
+            SourceError::MissingParent(_) => "missing-parent",
+            SourceError::InvalidParent(_) => "invalid-parent",
+            SourceError::AmbiguousType(..) => "ambiguous-type",
+            SourceError::UndefinedType(_) => "undefined-type",
+            SourceError::DuplicateAccessor(..) => "duplicate-accessor",
+            SourceError::DuplicateField(_) => "duplicate-field",
+            SourceError::InheritedField(..) => "inherited-field",
+            SourceError::IncludeCycle(_) => "include-cycle",
+            SourceError::InheritanceCycle(_) => "inheritance-cycle",
+            SourceError::UnmappedClass(..) => "unmapped-class",
+            SourceError::IgnoredLang => "ignored-lang",
+            SourceError::IgnoredFieldAccess => "ignored-field-access",
+        }
+    }
+
+    /// Short description of what is wrong at the span
+    pub fn label(&self) -> &'static str {
+        match self {
+            SourceError::IO(_)
+            | SourceError::Serialization(_)
+            | SourceError::Mappings(_)
+            | SourceError::MixinPackage(..)
+            | SourceError::IgnoredLang
+            | SourceError::IgnoredFieldAccess => "",
+            SourceError::Deserialization(_) => "could not be deserialized",
+            SourceError::AmbiguousClassOrEnum => "variants next to fields or methods",
+            SourceError::DuplicateVariant(_) => "repeated variant",
+            SourceError::MissingClass(_) => "static member without a class",
+            SourceError::MissingMixinTarget(_) | SourceError::MissingJniClass(_) => {
+                "object without a class"
+            }
+            SourceError::StopWithoutHierarchy(_) => "field doesn't search the hierarchy",
+            SourceError::UnsupportedMixinField(..) => "not supported in mixins",
+            SourceError::MissingParent(_) => "not found",
+            SourceError::InvalidParent(_) => "can't be extended",
+            SourceError::AmbiguousType(..) => "ambiguous type",
+            SourceError::UndefinedType(_) => "undefined type",
+            SourceError::DuplicateAccessor(..) => "generated twice",
+            SourceError::DuplicateField(_) => "repeated field name",
+            SourceError::InheritedField(..) => "hides a field of the extended object",
+            SourceError::IncludeCycle(_) => "included back",
+            SourceError::InheritanceCycle(_) => "extended back",
+            SourceError::UnmappedClass(..) => "not in the mappings",
+        }
+    }
+}
+
+/// Offset of the first appearance of `key` used as a key, followed by a colon
+fn find_key(source: &str, key: &str) -> Option<usize> {
+    keys(source, key).next()
+}
+
+/// Offsets of every appearance of `key` used as a key, followed by a colon
+fn keys<'a>(source: &'a str, key: &'a str) -> impl Iterator<Item = usize> + 'a {
+    source.match_indices(key).map(|(x, _)| x).filter(move |x| {
+        let before = source[..*x].chars().next_back();
+        let after = source[x + key.len()..].trim_start();
+        !is_identifier(before) && before != Some('"') && after.starts_with(':')
+    })
+}
+
+/// Offsets of `quoted` given as the value of any of the keys, either as is or in `Some(..)`
+fn key_values<'a>(
+    source: &'a str,
+    keys: &'a [&'a str],
+    quoted: &'a str,
+) -> impl Iterator<Item = usize> + 'a {
+    keys.iter().flat_map(move |key| {
+        self::keys(source, key).filter_map(move |x| {
+            let start = x + key.len();
+            value_after(source, start, ':', quoted).or_else(|| {
+                let some = value_after(source, start, ':', "Some")?;
+                value_after(source, some + "Some".len(), '(', quoted)
+            })
+        })
+    })
+}
+
+/// Offset of `needle` when it follows `separator` from the offset `start`, with any whitespace
+/// in between
+fn value_after(source: &str, start: usize, separator: char, needle: &str) -> Option<usize> {
+    let after = source[start..].trim_start().strip_prefix(separator)?;
+    let value = after.trim_start();
+    value
+        .starts_with(needle)
+        .then(|| source.len() - value.len())
+}
+
+fn is_identifier(character: Option<char>) -> bool {
+    character.is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"(
+    name: "Foo",
+    package: "acc",
+    extends: Some( "Bar" ),
+    fields: [
+        (name: "x", type: Object("Baz")),
+        (name: "x", type: List(Object( "Baz" ))),
+        (name: "y", rename: Some("z"), aliases: ["x"], type: i32),
+    ],
+)"#;
+
+    fn span(line: usize, column: usize, length: usize) -> Option<Span> {
+        Some(Span {
+            line,
+            column,
+            length,
+        })
+    }
+
+    #[test]
+    fn keys_skip_longer_names_and_strings() {
+        let source = r#"(rename: "name", name: "a", names: 1, "name": 2)"#;
+        assert_eq!(keys(source, "name").collect::<Vec<usize>>(), vec![17]);
+        assert_eq!(find_key(source, "rename"), Some(1));
+        assert_eq!(find_key(source, "package"), None);
+    }
+
+    #[test]
+    fn value_after_separator() {
+        let source = r#"name :  "a", Object( "b")"#;
+        assert_eq!(value_after(source, 4, ':', "\"a\""), Some(8));
+        assert_eq!(value_after(source, 19, '(', "\"b\""), Some(21));
+        assert_eq!(value_after(source, 4, '(', "\"a\""), None);
+        assert_eq!(value_after(source, 4, ':', "\"b\""), None);
+    }
+
+    #[test]
+    fn duplicate_field_ignores_aliases() {
+        let error = SourceError::DuplicateField("x".to_string());
+        assert_eq!(error.span(SOURCE), span(7, 16, 3));
+    }
+
+    #[test]
+    fn renamed_field() {
+        let error = SourceError::InheritedField("z".to_string(), "Bar".to_string());
+        assert_eq!(error.span(SOURCE), span(8, 34, 3));
+    }
+
+    #[test]
+    fn references() {
+        let error = SourceError::UndefinedType("Baz".to_string());
+        assert_eq!(error.span(SOURCE), span(6, 34, 5));
+        let error = SourceError::MissingParent("Bar".to_string());
+        assert_eq!(error.span(SOURCE), span(4, 20, 5));
+        // Only used as a parent
+        let error = SourceError::AmbiguousType("Bar".to_string(), String::new());
+        assert_eq!(error.span(SOURCE), span(4, 20, 5));
+    }
+
+    #[test]
+    fn missing_names() {
+        let error = SourceError::UndefinedType("Foo".to_string());
+        assert_eq!(error.span(SOURCE), None);
+        let error = SourceError::MissingClass("package".to_string());
+        assert_eq!(error.span(SOURCE), None);
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let error = SourceError::UnmappedClass("b".to_string(), String::new());
+        assert_eq!(error.span("(é: 1, name: \"b\")"), span(1, 14, 3));
+    }
+}
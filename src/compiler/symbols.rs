@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+
+use super::{Mappings, SourceError, ALLOWED_EXTENSIONS};
+use crate::Object;
+
+/// Schema parsed while indexing the project
+#[derive(Debug)]
+pub(super) struct Schema {
+    /// Path of the schema file, inside the target directory
+    pub path: PathBuf,
+    /// Canonical path of the schema file
+    canonical: PathBuf,
+    pub object: Object,
+}
+
+/// Every schema of the project, parsed once so objects can be referenced by name instead of
+/// by the path of their schema.
+#[derive(Debug, Default)]
+pub struct Symbols {
+    pub(super) schemas: Vec<Schema>,
+}
+
+impl Symbols {
+    /// Parses every schema in `target` recursively, with the mappings already applied.
+    /// Schemas that fail to parse are left out of the index and returned with their path, the
+    /// ones without a mapping are added to `warnings`.
+    pub fn index(
+        target: &Path,
+        mappings: Option<&Mappings>,
+        warnings: &mut Vec<(PathBuf, SourceError)>,
+    ) -> (Self, Vec<(PathBuf, SourceError)>) {
+        let mut symbols = Self::default();
+        let mut failed = vec![];
+        symbols.index_directory(target, mappings, &mut failed, warnings);
+
+        (symbols, failed)
+    }
+
+    fn index_directory(
+        &mut self,
+        target: &Path,
+        mappings: Option<&Mappings>,
+        failed: &mut Vec<(PathBuf, SourceError)>,
+        warnings: &mut Vec<(PathBuf, SourceError)>,
+    ) {
+        let read_dir = match target.read_dir() {
+            Ok(x) => x,
+            Err(err) => {
+                failed.push((target.to_path_buf(), SourceError::IO(err)));
+                return;
+            }
+        };
+
+        for path in read_dir {
+            let path = match path {
+                Ok(x) => x.path(),
+                Err(err) => {
+                    failed.push((PathBuf::new(), SourceError::IO(err)));
+                    continue;
+                }
+            };
+
+            if path.is_dir() {
+                self.index_directory(&path, mappings, failed, warnings);
+                continue;
+            }
+
+            let allowed = path
+                .extension()
+                .and_then(|x| x.to_str())
+                .is_some_and(|x| ALLOWED_EXTENSIONS.contains(&x));
+            if !allowed {
+                continue;
+            }
+
+            match parse(&path) {
+                Ok(mut object) => {
+                    if let Some(mappings) = mappings {
+                        if !mappings.apply(&mut object) {
+                            let unmapped = SourceError::UnmappedClass(
+                                object.name.clone(),
+                                mappings.path.display().to_string(),
+                            );
+                            warnings.push((path.clone(), unmapped));
+                        }
+                    }
+                    self.schemas.push(Schema {
+                        canonical: canonical(&path),
+                        path,
+                        object,
+                    });
+                }
+                Err(err) => failed.push((path, err)),
+            }
+        }
+    }
+
+    /// Object declared by the schema at `path`, if it was indexed
+    pub fn get(&self, path: &Path) -> Option<&Object> {
+        self.position(path).map(|x| &self.schemas[x].object)
+    }
+
+    /// Position in `schemas` of the schema at `path`, if it was indexed
+    pub(super) fn position(&self, path: &Path) -> Option<usize> {
+        let path = canonical(path);
+        self.schemas.iter().position(|x| x.canonical == path)
+    }
+
+    /// Resolves a reference to an object made from `from`, either by its name or by its
+    /// qualified `package.Name`. When several packages declare the name, the one in the package
+    /// of `from` is used, otherwise the reference has to be qualified.
+    pub fn resolve(&self, name: &str, from: &Object) -> Result<Option<&Object>, SourceError> {
+        Ok(self
+            .resolve_position(name, from)?
+            .map(|x| &self.schemas[x].object))
+    }
+
+    /// Same as [`Symbols::resolve`], with the position of the schema in `schemas`
+    pub(super) fn resolve_position(
+        &self,
+        name: &str,
+        from: &Object,
+    ) -> Result<Option<usize>, SourceError> {
+        let candidates = (0..self.schemas.len())
+            .filter(|x| self.schemas[*x].object.is_named(name))
+            .collect::<Vec<usize>>();
+
+        match candidates.as_slice() {
+            [] => Ok(None),
+            [position] => Ok(Some(*position)),
+            _ => match candidates
+                .iter()
+                .find(|x| self.schemas[**x].object.package == from.package)
+            {
+                Some(position) => Ok(Some(*position)),
+                None => Err(SourceError::AmbiguousType(
+                    name.to_string(),
+                    candidates
+                        .iter()
+                        .map(|x| self.schemas[*x].object.qualified_name())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                )),
+            },
+        }
+    }
+}
+
+fn parse(path: &Path) -> Result<Object, SourceError> {
+    let source = std::fs::read_to_string(path).map_err(SourceError::IO)?;
+    ron::from_str(&source).map_err(SourceError::Deserialization)
+}
+
+/// Paths are compared canonicalized, so includes like `../x.ron` match the indexed schema
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
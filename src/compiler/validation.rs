@@ -0,0 +1,238 @@
+use std::path::PathBuf;
+
+use super::{explicit_includes, Mappings, SourceError, Symbols};
+
+/// Checks the whole project before anything is generated, for the mistakes that would otherwise
+/// only show up when compiling the generated sources: references to undefined objects, accessors
+/// generated twice, repeated field names, including the ones of the extended objects, and cycles
+/// in inheritance. Cycles in includes are added to `warnings`.
+pub fn validate(
+    symbols: &Symbols,
+    mappings: Option<&Mappings>,
+    warnings: &mut Vec<(PathBuf, SourceError)>,
+) -> Vec<(PathBuf, SourceError)> {
+    let mut failed = vec![];
+    let mut includes = vec![vec![]; symbols.schemas.len()];
+    let mut parents = vec![vec![]; symbols.schemas.len()];
+
+    for (position, schema) in symbols.schemas.iter().enumerate() {
+        let object = &schema.object;
+        let working_directory = schema.path.parent().expect("File to have a parent");
+
+        let explicit = match explicit_includes(object, working_directory, symbols, mappings) {
+            Ok(x) => x,
+            Err(err) => {
+                failed.push((schema.path.clone(), err));
+                continue;
+            }
+        };
+        includes[position] = object
+            .includes
+            .iter()
+            .filter_map(|x| symbols.position(&working_directory.join(x)))
+            .collect();
+
+        let references = object
+            .types()
+            .flat_map(|x| x.objects(object))
+            .map(|x| (x, SourceError::UndefinedType(x.to_string())))
+            .chain(
+                object
+                    .extends
+                    .as_deref()
+                    .map(|x| (x, SourceError::MissingParent(x.to_string()))),
+            );
+        for (name, undefined) in references {
+            if explicit.iter().any(|x| x.is_named(name)) {
+                continue;
+            }
+            match symbols.resolve_position(name, object) {
+                Ok(Some(_)) => {}
+                Ok(None) => failed.push((schema.path.clone(), undefined)),
+                Err(err) => failed.push((schema.path.clone(), err)),
+            }
+        }
+
+        if let Some(parent) = &object.extends {
+            let included = includes[position]
+                .iter()
+                .find(|x| symbols.schemas[**x].object.is_named(parent))
+                .copied();
+            if let Some(parent) =
+                included.or(symbols.resolve_position(parent, object).ok().flatten())
+            {
+                parents[position].push(parent);
+            }
+        }
+
+        let mut field_names = std::collections::HashSet::new();
+        for field in &object.fields {
+            if !field_names.insert(field.display_name()) {
+                failed.push((
+                    schema.path.clone(),
+                    SourceError::DuplicateField(field.display_name().to_string()),
+                ));
+            }
+        }
+
+        // Accessors clash when they are generated into the same file or share a class name
+        let duplicate = symbols.schemas[..position].iter().find(|x| {
+            x.object.qualified_name() == object.qualified_name()
+                || (x.object.display_name() == object.display_name()
+                    && x.path.parent() == schema.path.parent())
+        });
+        if let Some(other) = duplicate {
+            failed.push((
+                schema.path.clone(),
+                SourceError::DuplicateAccessor(
+                    format!("{}Accessor", object.qualified_name()),
+                    other.path.display().to_string(),
+                ),
+            ));
+        }
+    }
+
+    for position in 0..symbols.schemas.len() {
+        // Includes are only resolved one level deep, so schemas including each other still
+        // compile, the cycle is only worth a warning
+        if let Some(cycle) = cycle(position, &includes) {
+            let cycle = SourceError::IncludeCycle(
+                cycle
+                    .iter()
+                    .map(|x| symbols.schemas[*x].path.display().to_string())
+                    .collect::<Vec<String>>()
+                    .join(" -> "),
+            );
+            warnings.push((symbols.schemas[position].path.clone(), cycle));
+        }
+        if let Some(cycle) = cycle(position, &parents) {
+            failed.push((
+                symbols.schemas[position].path.clone(),
+                SourceError::InheritanceCycle(
+                    cycle
+                        .iter()
+                        .map(|x| symbols.schemas[*x].object.display_name())
+                        .collect::<Vec<&str>>()
+                        .join(" -> "),
+                ),
+            ));
+        }
+
+        // Accessors extend the accessor of their parent, a field with the same name would hide
+        // the one of the parent and overload it's setter
+        let object = &symbols.schemas[position].object;
+        for ancestor in ancestors(position, &parents) {
+            let ancestor = &symbols.schemas[ancestor].object;
+            for field in &object.fields {
+                let inherited = ancestor
+                    .fields
+                    .iter()
+                    .any(|x| x.display_name() == field.display_name());
+                if inherited {
+                    failed.push((
+                        symbols.schemas[position].path.clone(),
+                        SourceError::InheritedField(
+                            field.display_name().to_string(),
+                            ancestor.display_name().to_string(),
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    failed
+}
+
+/// Positions extended by `start`, from it's parent up. Stops before visiting a position again,
+/// the cycle is reported on it's own.
+fn ancestors(start: usize, parents: &[Vec<usize>]) -> Vec<usize> {
+    let mut ancestors = vec![];
+    let mut current = start;
+
+    while let Some(&parent) = parents[current].first() {
+        if parent == start || ancestors.contains(&parent) {
+            break;
+        }
+        ancestors.push(parent);
+        current = parent;
+    }
+
+    ancestors
+}
+
+/// Cycle that goes through `start`, as the positions it visits from `start` back to it.
+/// Only positions after `start` are visited, so every cycle is reported once, from its first
+/// position.
+fn cycle(start: usize, edges: &[Vec<usize>]) -> Option<Vec<usize>> {
+    fn walk(
+        current: usize,
+        start: usize,
+        edges: &[Vec<usize>],
+        visited: &mut [bool],
+        path: &mut Vec<usize>,
+    ) -> bool {
+        for &next in &edges[current] {
+            if next < start {
+                continue;
+            }
+            path.push(next);
+            if next == start {
+                return true;
+            }
+            if !visited[next] {
+                visited[next] = true;
+                if walk(next, start, edges, visited, path) {
+                    return true;
+                }
+            }
+            path.pop();
+        }
+        false
+    }
+
+    let mut visited = vec![false; edges.len()];
+    let mut path = vec![start];
+    walk(start, start, edges, &mut visited, &mut path).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_cycle() {
+        assert_eq!(cycle(0, &[vec![]]), None);
+        // Diamond, two paths to the same position
+        let edges = [vec![1, 2], vec![3], vec![3], vec![]];
+        assert!((0..edges.len()).all(|x| cycle(x, &edges).is_none()));
+    }
+
+    #[test]
+    fn self_loop() {
+        assert_eq!(cycle(0, &[vec![0]]), Some(vec![0, 0]));
+    }
+
+    #[test]
+    fn cycle_reported_once() {
+        let edges = [vec![1], vec![2], vec![0]];
+        assert_eq!(cycle(0, &edges), Some(vec![0, 1, 2, 0]));
+        assert_eq!(cycle(1, &edges), None);
+        assert_eq!(cycle(2, &edges), None);
+    }
+
+    #[test]
+    fn cycle_not_through_start() {
+        let edges = [vec![1], vec![2], vec![1]];
+        assert_eq!(cycle(0, &edges), None);
+        assert_eq!(cycle(1, &edges), Some(vec![1, 2, 1]));
+    }
+
+    #[test]
+    fn ancestors_stop_at_cycles() {
+        assert_eq!(ancestors(0, &[vec![1], vec![2], vec![]]), vec![1, 2]);
+        assert_eq!(ancestors(0, &[vec![1], vec![0]]), vec![1]);
+        assert_eq!(ancestors(0, &[vec![1], vec![2], vec![1]]), vec![1, 2]);
+        assert_eq!(ancestors(0, &[vec![0]]), Vec::<usize>::new());
+    }
+}
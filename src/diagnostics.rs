@@ -0,0 +1,127 @@
+use std::path::Path;
+
+use colored::*;
+use serde::Serialize;
+
+use crate::compiler::{SourceError, Span};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+pub enum MessageFormat {
+    /// Errors with the line of the schema they are about, for people
+    #[default]
+    Human,
+    /// A JSON object per line for every error, for editors and other tools
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Nothing is generated
+    Error,
+    /// Generated anyway, but likely not what was intended
+    Warning,
+}
+
+/// Error or warning as emitted with the JSON message format
+#[derive(Serialize)]
+struct Diagnostic {
+    file: String,
+    severity: Severity,
+    code: &'static str,
+    message: String,
+    label: &'static str,
+    span: Option<Span>,
+}
+
+/// Prints an error or warning in the selected format
+/// * `source` - Contents of the schema, when it could be read
+pub fn emit(
+    format: MessageFormat,
+    severity: Severity,
+    path: &Path,
+    error: &SourceError,
+    source: Option<&str>,
+) {
+    match format {
+        MessageFormat::Human => println!("{}", render(severity, path, error, source)),
+        MessageFormat::Json => {
+            let diagnostic = Diagnostic {
+                file: path.display().to_string(),
+                severity,
+                code: error.code(),
+                message: error.to_string(),
+                label: error.label(),
+                span: source.and_then(|x| error.span(x)),
+            };
+            println!(
+                "{}",
+                serde_json::to_string(&diagnostic).expect("Diagnostic to serialize")
+            );
+        }
+    }
+}
+
+/// Renders an error like rustc does, with the line of the schema it is about and a caret
+/// under the span.
+/// * `source` - Contents of the schema, when it could be read
+fn render(severity: Severity, path: &Path, error: &SourceError, source: Option<&str>) -> String {
+    let color = match severity {
+        Severity::Error => Color::Red,
+        Severity::Warning => Color::Yellow,
+    };
+    let name = match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+    let mut output = format!(
+        "{}: {}\n",
+        name.color(color).bold(),
+        error.to_string().bold()
+    );
+
+    let span = source.and_then(|source| Some((source, error.span(source)?)));
+    let Some((source, span)) = span else {
+        output.push_str(&format!("  {} {}\n", "-->".blue().bold(), path.display()));
+        return output;
+    };
+
+    let line = source.lines().nth(span.line - 1).unwrap_or_default();
+    let number = span.line.to_string();
+    let gutter = " ".repeat(number.len());
+
+    // Tabs are kept so the caret lines up with the text above it
+    let padding = line
+        .chars()
+        .take(span.column - 1)
+        .map(|x| if x == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+    let length = span
+        .length
+        .min(line.chars().count().saturating_sub(span.column - 1))
+        .max(1);
+
+    output.push_str(&format!(
+        "{gutter}{} {}:{}:{}\n",
+        "-->".blue().bold(),
+        path.display(),
+        span.line,
+        span.column
+    ));
+    output.push_str(&format!("{gutter} {}\n", "|".blue().bold()));
+    output.push_str(&format!(
+        "{} {} {line}\n",
+        number.blue().bold(),
+        "|".blue().bold()
+    ));
+    output.push_str(&format!(
+        "{gutter} {} {padding}{}\n",
+        "|".blue().bold(),
+        format!("{} {}", "^".repeat(length), error.label())
+            .trim_end()
+            .color(color)
+            .bold()
+    ));
+
+    output
+}
@@ -12,15 +12,18 @@ pub struct Class {
     pub is_virtual: bool,
     pub package: String,
     pub comment: Option<String>,
+    /// Superclass of the class
+    pub extends: Option<String>,
     #[builder(default)]
     pub fields: Vec<Field>,
     #[builder(default)]
     pub methods: Vec<Method>,
     #[builder(default)]
     pub includes: Vec<String>,
+    #[builder(default)]
+    pub annotations: Vec<String>,
 }
 
-#[allow(unused)]
 #[derive(Default, Debug)]
 pub enum ClassType {
     #[default]
@@ -49,6 +52,9 @@ impl Class {
         }
 
         writeln!(output, "\n\n@SuppressWarnings(\"all\")").unwrap();
+        for annotation in &self.annotations {
+            writeln!(output, "{annotation}").unwrap();
+        }
         write!(output, "public ").unwrap();
         if self.is_virtual {
             write!(output, "virtual ").unwrap();
@@ -57,29 +63,32 @@ impl Class {
             ClassType::Class => write!(output, "class ").unwrap(),
             ClassType::Interface => write!(output, "interface ").unwrap(),
         }
-        writeln!(output, "{} {{", self.name).unwrap();
+        write!(output, "{}", self.name).unwrap();
+        if let Some(extends) = &self.extends {
+            write!(output, " extends {extends}").unwrap();
+        }
+        writeln!(output, " {{").unwrap();
 
         for field in &self.fields {
             if let Some(comment) = &field.comment {
                 let lines: Vec<String> = comment.lines().map(|x| format!("\t// {x}\n")).collect();
                 write!(output, "{}", lines.join("\n")).unwrap();
             }
-            writeln!(
-                output,
-                "\t{} {} {};",
-                field.visibility,
-                field.type_name,
-                field.name
-            )
-            .unwrap();
+            writeln!(output, "\t{}", field.declaration()).unwrap();
         }
 
         if !self.methods.is_empty() {
             for method in &self.methods {
+                for annotation in &method.annotations {
+                    write!(output, "\n\t{annotation}").unwrap();
+                }
                 write!(output, "\n\t{}", method.visibility).unwrap();
                 if method.is_static {
                     write!(output, " static").unwrap();
                 }
+                if method.is_abstract {
+                    write!(output, " abstract").unwrap();
+                }
 
                 if !method.is_constructor {
                     write!(
@@ -104,6 +113,11 @@ impl Class {
                     write!(output, " throws {}", method.exceptions.join(", ")).unwrap();
                 }
 
+                if method.is_abstract {
+                    writeln!(output, ";").unwrap();
+                    continue;
+                }
+
                 write!(output, " {{").unwrap();
                 {
                     let lines: Vec<String> =
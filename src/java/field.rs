@@ -10,4 +10,35 @@ pub struct Field {
     pub comment: Option<String>,
     #[builder(default)]
     pub visibility: Visibility,
+    #[builder(default)]
+    pub is_static: bool,
+    #[builder(default)]
+    pub is_final: bool,
+    #[builder(default)]
+    pub is_volatile: bool,
+    /// Initializer of the field
+    pub value: Option<String>,
+}
+
+impl Field {
+    pub fn declaration(&self) -> String {
+        let mut declaration = self.visibility.to_string();
+        if self.is_static {
+            declaration.push_str(" static");
+        }
+        if self.is_final {
+            declaration.push_str(" final");
+        }
+        if self.is_volatile {
+            declaration.push_str(" volatile");
+        }
+
+        declaration = format!("{declaration} {} {}", self.type_name, self.name);
+
+        if let Some(value) = &self.value {
+            declaration = format!("{declaration} = {value}");
+        }
+
+        format!("{declaration};")
+    }
 }
@@ -1,6 +1,6 @@
 use bon::Builder;
 
-use super::Method;
+use super::{Field, Method};
 
 #[derive(Builder, Debug)]
 #[builder(on(String, into))]
@@ -11,6 +11,8 @@ pub struct Enum {
     #[builder(default)]
     pub variants: Vec<String>,
     #[builder(default)]
+    pub fields: Vec<Field>,
+    #[builder(default)]
     pub methods: Vec<Method>,
     #[builder(default)]
     pub includes: Vec<String>,
@@ -48,14 +50,33 @@ impl Enum {
 
         writeln!(output, "{variants};").unwrap();
 
+        if !self.fields.is_empty() {
+            writeln!(output).unwrap();
+
+            for field in &self.fields {
+                if let Some(comment) = &field.comment {
+                    let lines: Vec<String> =
+                        comment.lines().map(|x| format!("\t// {x}\n")).collect();
+                    write!(output, "{}", lines.join("\n")).unwrap();
+                }
+                writeln!(output, "\t{}", field.declaration()).unwrap();
+            }
+        }
+
         if !self.methods.is_empty() {
             writeln!(output).unwrap();
 
             for method in &self.methods {
+                for annotation in &method.annotations {
+                    write!(output, "\n\t{annotation}").unwrap();
+                }
                 write!(output, "\n\t{}", method.visibility).unwrap();
                 if method.is_static {
                     write!(output, " static").unwrap();
                 }
+                if method.is_abstract {
+                    write!(output, " abstract").unwrap();
+                }
 
                 if !method.is_constructor {
                     write!(
@@ -80,6 +101,11 @@ impl Enum {
                     write!(output, " throws {}", method.exceptions.join(", ")).unwrap();
                 }
 
+                if method.is_abstract {
+                    writeln!(output, ";").unwrap();
+                    continue;
+                }
+
                 write!(output, " {{").unwrap();
                 {
                     let lines: Vec<String> =
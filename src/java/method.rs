@@ -17,6 +17,12 @@ pub struct Method {
     pub is_static: bool,
     #[builder(default)]
     pub exceptions: Vec<String>,
+    #[builder(default)]
+    pub annotations: Vec<String>,
+    /// Abstract methods are declared without a body
+    #[builder(default)]
+    pub is_abstract: bool,
+    #[builder(default)]
     pub body: String,
 }
 
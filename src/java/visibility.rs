@@ -4,6 +4,7 @@ use std::fmt::Display;
 pub enum Visibility {
     #[default]
     Public,
+    Protected,
     Private,
 }
 
@@ -11,6 +12,7 @@ impl Display for Visibility {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Visibility::Public => write!(f, "public"),
+            Visibility::Protected => write!(f, "protected"),
             Visibility::Private => write!(f, "private"),
         }
     }
@@ -0,0 +1,65 @@
+use bon::Builder;
+
+use super::{Function, Parameter, Property};
+
+#[derive(Builder, Debug)]
+#[builder(on(String, into))]
+pub struct Class {
+    pub name: String,
+    pub package: String,
+    pub comment: Option<String>,
+    /// Plain parameters of the primary constructor, before the properties
+    #[builder(default)]
+    pub parameters: Vec<Parameter>,
+    /// Properties declared in the primary constructor
+    #[builder(default)]
+    pub constructor: Vec<Property>,
+    /// Superclass, with the arguments of its constructor
+    pub superclass: Option<String>,
+    #[builder(default)]
+    pub properties: Vec<Property>,
+    #[builder(default)]
+    pub functions: Vec<Function>,
+    #[builder(default)]
+    pub companion_properties: Vec<Property>,
+    #[builder(default)]
+    pub companion_functions: Vec<Function>,
+    #[builder(default)]
+    pub includes: Vec<String>,
+}
+
+impl Class {
+    pub fn source(&self) -> String {
+        let mut output = super::header(&self.package, &self.includes, &self.comment);
+
+        let constructor = self
+            .parameters
+            .iter()
+            .map(|x| format!("{}: {}", x.name, x.type_name))
+            .chain(self.constructor.iter().map(|x| x.declaration()))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let superclass = match &self.superclass {
+            Some(x) => format!(" : {x}"),
+            None => String::new(),
+        };
+        // Classes are open, since other accessors can extend them
+        output.push_str(&format!(
+            "open class {}({constructor}){superclass} {{\n",
+            self.name
+        ));
+
+        let body = super::members(&self.properties, &self.functions, 1)
+            .into_iter()
+            .chain(super::companion(
+                &self.companion_properties,
+                &self.companion_functions,
+            ))
+            .collect::<Vec<String>>();
+        output.push_str(&body.join("\n\n"));
+
+        output.push_str("\n}\n");
+
+        output
+    }
+}
@@ -0,0 +1,65 @@
+use bon::Builder;
+
+use crate::java::Visibility;
+
+#[derive(Builder, Debug)]
+#[builder(on(String, into))]
+pub struct Function {
+    pub name: String,
+    #[builder(default)]
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<String>,
+    #[builder(default)]
+    pub visibility: Visibility,
+    #[builder(default)]
+    pub annotations: Vec<String>,
+    /// Can be overridden by subclasses
+    #[builder(default)]
+    pub is_open: bool,
+    #[builder(default)]
+    pub is_override: bool,
+    pub body: String,
+}
+
+#[derive(Builder, Debug)]
+#[builder(on(String, into))]
+pub struct Parameter {
+    pub name: String,
+    pub type_name: String,
+}
+
+impl Function {
+    /// Lines of the function, indented with `indent` tabs
+    pub fn source(&self, indent: usize) -> String {
+        let tabs = "\t".repeat(indent);
+        let mut lines = vec![];
+
+        lines.extend(self.annotations.iter().map(|x| format!("{tabs}{x}")));
+
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|x| format!("{}: {}", x.name, x.type_name))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let return_type = match &self.return_type {
+            Some(x) => format!(": {x}"),
+            None => String::new(),
+        };
+        let modifier = if self.is_override {
+            " override"
+        } else if self.is_open {
+            " open"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "{tabs}{}{modifier} fun {}({parameters}){return_type} {{",
+            self.visibility, self.name
+        ));
+        lines.extend(self.body.trim().lines().map(|x| format!("{tabs}\t{x}")));
+        lines.push(format!("{tabs}}}"));
+
+        lines.join("\n")
+    }
+}
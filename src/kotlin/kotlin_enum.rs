@@ -0,0 +1,60 @@
+use bon::Builder;
+
+use super::{Function, Property};
+
+#[derive(Builder, Debug)]
+#[builder(on(String, into))]
+pub struct Enum {
+    pub name: String,
+    pub package: String,
+    pub comment: Option<String>,
+    /// Properties declared in the primary constructor
+    #[builder(default)]
+    pub constructor: Vec<Property>,
+    #[builder(default)]
+    pub variants: Vec<String>,
+    #[builder(default)]
+    pub properties: Vec<Property>,
+    #[builder(default)]
+    pub functions: Vec<Function>,
+    #[builder(default)]
+    pub companion_properties: Vec<Property>,
+    #[builder(default)]
+    pub companion_functions: Vec<Function>,
+    #[builder(default)]
+    pub includes: Vec<String>,
+}
+
+impl Enum {
+    pub fn source(&self) -> String {
+        let mut output = super::header(&self.package, &self.includes, &self.comment);
+
+        let constructor = self
+            .constructor
+            .iter()
+            .map(|x| x.declaration())
+            .collect::<Vec<String>>()
+            .join(", ");
+        output.push_str(&format!("enum class {}({constructor}) {{\n", self.name));
+
+        let variants = self
+            .variants
+            .iter()
+            .map(|x| format!("\t{x}"))
+            .collect::<Vec<String>>()
+            .join(",\n");
+
+        let body = std::iter::once(format!("{variants};"))
+            .chain(super::members(&self.properties, &self.functions, 1))
+            .chain(super::companion(
+                &self.companion_properties,
+                &self.companion_functions,
+            ))
+            .collect::<Vec<String>>();
+        output.push_str(&body.join("\n\n"));
+
+        output.push_str("\n}\n");
+
+        output
+    }
+}
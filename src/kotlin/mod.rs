@@ -0,0 +1,58 @@
+mod class;
+pub use class::*;
+mod property;
+pub use property::*;
+mod function;
+pub use function::*;
+mod kotlin_enum;
+pub use kotlin_enum::*;
+
+/// Warnings the generated code triggers on purpose, like unchecked casts of reflected values
+const SUPPRESSED: &str = r#"@file:Suppress("UNCHECKED_CAST", "NAME_SHADOWING", "UNUSED_VARIABLE", "unused", "RedundantVisibilityModifier")"#;
+
+/// File annotations, package, imports and comment, up to the type declaration
+fn header(package: &str, includes: &[String], comment: &Option<String>) -> String {
+    use std::fmt::Write;
+    let mut output = String::new();
+
+    writeln!(output, "{SUPPRESSED}\n").unwrap();
+    writeln!(output, "package {package}").unwrap();
+
+    if !includes.is_empty() {
+        writeln!(output).unwrap();
+        for include in includes {
+            writeln!(output, "import {include}").unwrap();
+        }
+    }
+
+    if let Some(comment) = comment {
+        writeln!(output).unwrap();
+        let lines: Vec<String> = comment.lines().map(|x| format!("// {x}")).collect();
+        write!(output, "{}", lines.join("\n")).unwrap();
+    }
+
+    writeln!(output, "\n").unwrap();
+
+    output
+}
+
+/// Properties and functions of a type body, each separated by an empty line
+fn members(properties: &[Property], functions: &[Function], indent: usize) -> Vec<String> {
+    properties
+        .iter()
+        .map(|x| x.source(indent))
+        .chain(functions.iter().map(|x| x.source(indent)))
+        .collect()
+}
+
+/// The companion object, when it has any member
+fn companion(properties: &[Property], functions: &[Function]) -> Option<String> {
+    if properties.is_empty() && functions.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "\tcompanion object {{\n{}\n\t}}",
+        members(properties, functions, 2).join("\n\n")
+    ))
+}
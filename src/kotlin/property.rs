@@ -0,0 +1,62 @@
+use bon::Builder;
+
+use crate::java::Visibility;
+
+#[derive(Builder, Debug, Clone)]
+#[builder(on(String, into))]
+pub struct Property {
+    pub name: String,
+    pub type_name: String,
+    pub comment: Option<String>,
+    #[builder(default)]
+    pub visibility: Visibility,
+    /// `var` instead of `val`
+    #[builder(default)]
+    pub is_mutable: bool,
+    #[builder(default)]
+    pub annotations: Vec<String>,
+    /// Initializer of the property
+    pub value: Option<String>,
+    /// Body of the custom getter
+    pub getter: Option<String>,
+    /// Body of the custom setter, the new value is named `value`
+    pub setter: Option<String>,
+}
+
+impl Property {
+    pub fn declaration(&self) -> String {
+        let keyword = if self.is_mutable { "var" } else { "val" };
+        let mut declaration = format!(
+            "{} {keyword} {}: {}",
+            self.visibility, self.name, self.type_name
+        );
+
+        if let Some(value) = &self.value {
+            declaration = format!("{declaration} = {value}");
+        }
+
+        declaration
+    }
+
+    /// Lines of the property, indented with `indent` tabs
+    pub fn source(&self, indent: usize) -> String {
+        let tabs = "\t".repeat(indent);
+        let mut lines = vec![];
+
+        if let Some(comment) = &self.comment {
+            lines.extend(comment.lines().map(|x| format!("{tabs}// {x}")));
+        }
+        lines.extend(self.annotations.iter().map(|x| format!("{tabs}{x}")));
+        lines.push(format!("{tabs}{}", self.declaration()));
+
+        for (header, body) in [("get()", &self.getter), ("set(value)", &self.setter)] {
+            if let Some(body) = body {
+                lines.push(format!("{tabs}\t{header} {{"));
+                lines.extend(body.trim().lines().map(|x| format!("{tabs}\t\t{x}")));
+                lines.push(format!("{tabs}\t}}"));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
@@ -1,11 +1,17 @@
-use std::path::Path;
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
 use clap::Parser;
+use diagnostics::{MessageFormat, Severity};
 use serde::{Deserialize, Serialize};
 
 mod cli;
 mod compiler;
+mod diagnostics;
 mod java;
+mod kotlin;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 enum Type {
@@ -25,10 +31,24 @@ enum Type {
     F32,
     #[serde(rename = "f64")]
     F64,
+    #[serde(rename = "char")]
+    Char,
+    Boolean,
+    Integer,
+    Long,
+    Byte,
+    Short,
+    Float,
+    Double,
+    Character,
     #[serde(rename = "self")]
     SelfObject,
     Object(String),
     Array(Box<Type>),
+    List(Box<Type>),
+    Set(Box<Type>),
+    Map(Box<Type>, Box<Type>),
+    Optional(Box<Type>),
 }
 
 impl Type {
@@ -42,20 +62,251 @@ impl Type {
             Type::I16 => "short".to_string(),
             Type::F32 => "float".to_string(),
             Type::F64 => "double".to_string(),
+            Type::Char => "char".to_string(),
+            Type::Boolean => "Boolean".to_string(),
+            Type::Integer => "Integer".to_string(),
+            Type::Long => "Long".to_string(),
+            Type::Byte => "Byte".to_string(),
+            Type::Short => "Short".to_string(),
+            Type::Float => "Float".to_string(),
+            Type::Double => "Double".to_string(),
+            Type::Character => "Character".to_string(),
             Type::Object(name) => format!("{name}Accessor"),
             Type::Array(array_type) => match array_type.as_ref().clone() {
                 Type::Object(name) => format!("{name}Accessor[]"),
                 Type::Array(array_type) => format!("{}[][]", array_type.java_name(object)),
                 other => format!("{}[]", other.java_name(object)),
             },
-            Type::SelfObject => format!("{}Accessor", object.name),
+            Type::SelfObject => format!("{}Accessor", object.display_name()),
+            Type::List(inner) => format!("java.util.List<{}>", inner.boxed_java_name(object)),
+            Type::Set(inner) => format!("java.util.Set<{}>", inner.boxed_java_name(object)),
+            Type::Map(key, value) => format!(
+                "java.util.Map<{}, {}>",
+                key.boxed_java_name(object),
+                value.boxed_java_name(object)
+            ),
+            Type::Optional(inner) => {
+                format!("java.util.Optional<{}>", inner.boxed_java_name(object))
+            }
+        }
+    }
+
+    /// Java name of the type when used as a generic argument, where primitives have to be boxed.
+    pub fn boxed_java_name(&self, object: &Object) -> String {
+        match self {
+            Type::Bool => "Boolean".to_string(),
+            Type::I32 => "Integer".to_string(),
+            Type::I64 => "Long".to_string(),
+            Type::U8 => "Byte".to_string(),
+            Type::I16 => "Short".to_string(),
+            Type::F32 => "Float".to_string(),
+            Type::F64 => "Double".to_string(),
+            Type::Char => "Character".to_string(),
+            other => other.java_name(object),
+        }
+    }
+
+    /// Java name of the type as declared by the original class, used where the original types are
+    /// needed instead of the accessors, like mixins.
+    /// Fails with the name of the referenced object when it doesn't declare it's original class.
+    pub fn original_java_name(
+        &self,
+        object: &Object,
+        includes: &[Object],
+    ) -> Result<String, String> {
+        let class_of = |target: &Object| {
+            target
+                .class_name
+                .as_ref()
+                // Nested classes are referenced with dots in the source
+                .map(|x| x.replace('$', "."))
+                .ok_or_else(|| target.display_name().to_string())
+        };
+        let boxed = |inner: &Type| match inner {
+            Type::Bool
+            | Type::I32
+            | Type::I64
+            | Type::U8
+            | Type::I16
+            | Type::F32
+            | Type::F64
+            | Type::Char => Ok(inner.boxed_java_name(object)),
+            other => other.original_java_name(object, includes),
+        };
+
+        match self {
+            Type::SelfObject => class_of(object),
+            // The object itself isn't part of its includes
+            Type::Object(name) if object.is_named(name) => class_of(object),
+            Type::Object(name) => includes
+                .iter()
+                .find(|x| x.is_named(name))
+                .ok_or_else(|| name.clone())
+                .and_then(class_of),
+            Type::Array(inner) => Ok(format!("{}[]", inner.original_java_name(object, includes)?)),
+            Type::List(inner) => Ok(format!("java.util.List<{}>", boxed(inner)?)),
+            Type::Set(inner) => Ok(format!("java.util.Set<{}>", boxed(inner)?)),
+            Type::Map(key, value) => {
+                Ok(format!("java.util.Map<{}, {}>", boxed(key)?, boxed(value)?))
+            }
+            Type::Optional(inner) => Ok(format!("java.util.Optional<{}>", boxed(inner)?)),
+            other => Ok(other.java_name(object)),
+        }
+    }
+
+    /// JNI descriptor of the original type, e.g. `I`, `[B` or `Lcom/x/Foo;`.
+    /// Err is the name of an object that didn't declare it's class.
+    pub fn jni_signature(&self, object: &Object, includes: &[Object]) -> Result<String, String> {
+        let class_of = |target: &Object| {
+            target
+                .class_name
+                .as_ref()
+                .map(|x| format!("L{};", x.replace('.', "/")))
+                .ok_or_else(|| target.display_name().to_string())
+        };
+
+        let signature = match self {
+            Type::Bool => "Z",
+            Type::I32 => "I",
+            Type::I64 => "J",
+            Type::U8 => "B",
+            Type::I16 => "S",
+            Type::F32 => "F",
+            Type::F64 => "D",
+            Type::Char => "C",
+            Type::String => "Ljava/lang/String;",
+            Type::Boolean => "Ljava/lang/Boolean;",
+            Type::Integer => "Ljava/lang/Integer;",
+            Type::Long => "Ljava/lang/Long;",
+            Type::Byte => "Ljava/lang/Byte;",
+            Type::Short => "Ljava/lang/Short;",
+            Type::Float => "Ljava/lang/Float;",
+            Type::Double => "Ljava/lang/Double;",
+            Type::Character => "Ljava/lang/Character;",
+            // Generics are erased
+            Type::List(_) => "Ljava/util/List;",
+            Type::Set(_) => "Ljava/util/Set;",
+            Type::Map(_, _) => "Ljava/util/Map;",
+            Type::Optional(_) => "Ljava/util/Optional;",
+            Type::SelfObject => return class_of(object),
+            // The object itself isn't part of its includes
+            Type::Object(name) if object.is_named(name) => return class_of(object),
+            Type::Object(name) => {
+                return includes
+                    .iter()
+                    .find(|x| x.is_named(name))
+                    .ok_or_else(|| name.clone())
+                    .and_then(class_of)
+            }
+            Type::Array(inner) => {
+                return Ok(format!("[{}", inner.jni_signature(object, includes)?))
+            }
+        };
+
+        Ok(signature.to_string())
+    }
+
+    /// Identifier-safe name of the type, used to name the generated collection accessors.
+    pub fn mangled_name(&self, object: &Object) -> String {
+        match self {
+            Type::Array(inner) => format!("ArrayOf{}", inner.mangled_name(object)),
+            Type::List(inner) => format!("ListOf{}", inner.mangled_name(object)),
+            Type::Set(inner) => format!("SetOf{}", inner.mangled_name(object)),
+            Type::Map(key, value) => format!(
+                "MapOf{}And{}",
+                key.mangled_name(object),
+                value.mangled_name(object)
+            ),
+            Type::Optional(inner) => format!("OptionalOf{}", inner.mangled_name(object)),
+            // Qualified names keep their package, without the dots
+            Type::Object(name) => name.replace('.', "_"),
+            Type::SelfObject => object.display_name().to_string(),
+            // Primitives keep their own name, so they don't clash with their boxed types
+            other => other.java_name(object),
+        }
+    }
+
+    pub fn is_collection(&self) -> bool {
+        matches!(
+            self,
+            Type::List(_) | Type::Set(_) | Type::Map(_, _) | Type::Optional(_)
+        )
+    }
+
+    /// Whether turning a value into this type needs a generated `access{mangled_name}` method:
+    /// collections, and arrays whose elements aren't primitives or plain objects.
+    pub fn has_access_method(&self) -> bool {
+        match self {
+            Type::Array(inner) => {
+                (self.is_nested_array() && !self.is_primitive()) || inner.has_access_method()
+            }
+            other => other.is_collection(),
+        }
+    }
+
+    /// Every type nested in this type that needs an access method, innermost first, including
+    /// itself.
+    pub fn access_methods(&self) -> Vec<&Type> {
+        self.nested_types()
+            .into_iter()
+            .filter(|x| x.has_access_method())
+            .collect()
+    }
+
+    /// Whether turning an accessor value back into the original value needs a generated
+    /// `unwrap{mangled_name}` method: collections and arrays holding accessors.
+    pub fn has_unwrap_method(&self) -> bool {
+        !self.is_primitive() && (self.is_collection() || matches!(self, Type::Array(_)))
+    }
+
+    /// Every type nested in this type that needs an unwrap method, innermost first, including
+    /// itself.
+    pub fn unwrap_methods(&self) -> Vec<&Type> {
+        self.nested_types()
+            .into_iter()
+            .filter(|x| x.has_unwrap_method())
+            .collect()
+    }
+
+    /// Every type nested in this type, innermost first, including itself.
+    fn nested_types(&self) -> Vec<&Type> {
+        let mut types = match self {
+            Type::Array(inner) | Type::List(inner) | Type::Set(inner) | Type::Optional(inner) => {
+                inner.nested_types()
+            }
+            Type::Map(key, value) => key
+                .nested_types()
+                .into_iter()
+                .chain(value.nested_types())
+                .collect(),
+            _ => vec![],
+        };
+
+        types.push(self);
+        types
+    }
+
+    /// Names of every object referenced by this type.
+    pub fn objects<'a>(&'a self, object: &'a Object) -> Vec<&'a str> {
+        match self {
+            Type::Object(name) => vec![name.as_str()],
+            Type::SelfObject => vec![object.display_name()],
+            Type::Array(inner) | Type::List(inner) | Type::Set(inner) | Type::Optional(inner) => {
+                inner.objects(object)
+            }
+            Type::Map(key, value) => key
+                .objects(object)
+                .into_iter()
+                .chain(value.objects(object))
+                .collect(),
+            _ => vec![],
         }
     }
 
     fn ends_in_object<'a>(&'a self, object: &'a Object) -> Option<&'a str> {
         match self {
             Type::Object(name) => Some(name),
-            Type::SelfObject => Some(&object.name),
+            Type::SelfObject => Some(object.display_name()),
             Type::Array(array) => array.ends_in_object(object),
             _ => None,
         }
@@ -79,22 +330,106 @@ impl Type {
             | Type::U8
             | Type::I16
             | Type::F32
-            | Type::F64 => true,
+            | Type::F64
+            | Type::Char
+            | Type::Boolean
+            | Type::Integer
+            | Type::Long
+            | Type::Byte
+            | Type::Short
+            | Type::Float
+            | Type::Double
+            | Type::Character => true,
             Type::SelfObject => false,
             Type::Object(_) => false,
             Type::Array(arr) => arr.is_primitive(),
+            Type::List(inner) | Type::Set(inner) | Type::Optional(inner) => inner.is_primitive(),
+            Type::Map(key, value) => key.is_primitive() && value.is_primitive(),
         }
     }
 
-    pub fn generate_accessor(&self, root: bool, object: &Object) -> String {
-        if let Some(name) = self.ends_in_object(object) {
-            if self.is_nested_array() {
-                return format!(
-                    "({}){name}Accessor.accessArrayNested(value)",
-                    self.java_name(object)
-                );
-            }
+    /// Method of `java.lang.reflect.Field` that reads a field of this type.
+    /// Only true primitives have a dedicated getter, everything else (boxed types included) is read
+    /// as an `Object` through `get`.
+    pub fn field_getter(&self) -> &'static str {
+        match self {
+            Type::Bool => "getBoolean",
+            Type::I32 => "getInt",
+            Type::I64 => "getLong",
+            Type::U8 => "getByte",
+            Type::I16 => "getShort",
+            Type::F32 => "getFloat",
+            Type::F64 => "getDouble",
+            Type::Char => "getChar",
+            Type::String
+            | Type::Boolean
+            | Type::Integer
+            | Type::Long
+            | Type::Byte
+            | Type::Short
+            | Type::Float
+            | Type::Double
+            | Type::Character
+            | Type::Array(_)
+            | Type::Object(_)
+            | Type::SelfObject
+            | Type::List(_)
+            | Type::Set(_)
+            | Type::Map(_, _)
+            | Type::Optional(_) => "get",
+        }
+    }
+
+    /// Java default value of the type, what an unset field of this type holds.
+    pub fn default_value(&self) -> &'static str {
+        match self {
+            Type::Bool => "false",
+            Type::I32 | Type::U8 | Type::I16 => "0",
+            Type::I64 => "0L",
+            Type::F32 => "0.0f",
+            Type::F64 => "0.0",
+            Type::Char => "'\\0'",
+            _ => "null",
+        }
+    }
 
+    /// Java class literal of the type, when it's the same for the original value and the accessor.
+    /// Used to match the parameters of methods.
+    pub fn java_class(&self) -> Option<String> {
+        match self {
+            Type::Object(_)
+            | Type::SelfObject
+            | Type::List(_)
+            | Type::Set(_)
+            | Type::Map(_, _)
+            | Type::Optional(_) => None,
+            Type::Array(inner) => inner.java_class().map(|x| x.replace(".class", "[].class")),
+            Type::Bool => Some("boolean.class".to_string()),
+            Type::I32 => Some("int.class".to_string()),
+            Type::I64 => Some("long.class".to_string()),
+            Type::String => Some("String.class".to_string()),
+            Type::U8 => Some("byte.class".to_string()),
+            Type::I16 => Some("short.class".to_string()),
+            Type::F32 => Some("float.class".to_string()),
+            Type::F64 => Some("double.class".to_string()),
+            Type::Char => Some("char.class".to_string()),
+            Type::Boolean => Some("Boolean.class".to_string()),
+            Type::Integer => Some("Integer.class".to_string()),
+            Type::Long => Some("Long.class".to_string()),
+            Type::Byte => Some("Byte.class".to_string()),
+            Type::Short => Some("Short.class".to_string()),
+            Type::Float => Some("Float.class".to_string()),
+            Type::Double => Some("Double.class".to_string()),
+            Type::Character => Some("Character.class".to_string()),
+        }
+    }
+
+    pub fn generate_accessor(&self, object: &Object) -> String {
+        if self.has_access_method() {
+            return self.access_expression("value", object);
+        }
+
+        if let Some(name) = self.ends_in_object(object) {
             if let Type::Array(_) = self {
                 return format!(
                     "({}){name}Accessor.accessArray((Object[])value)",
@@ -103,39 +438,202 @@ impl Type {
             }
         }
 
-        let cast = match self {
+        match self {
             Type::Object(name) => format!("{name}Accessor.access(value)"),
-            Type::SelfObject => format!("{}Accessor.access(value)", object.name),
-            Type::Array(array) => format!("{}[]", array.generate_accessor(false, object)),
-            other => other.java_name(object).to_string(),
+            Type::SelfObject => format!("{}Accessor.access(value)", object.display_name()),
+            // Anything else, including arrays of primitives, is cast to it's real static type
+            other => format!("({})value", other.java_name(object)),
+        }
+    }
+
+    /// Java expression turning `value`, an accessor value of this type, back into the value the
+    /// original object holds.
+    /// * `class` - Expression of the real class of the value, when known. Used to resolve enum
+    ///   constants and to create arrays of the right type.
+    /// * `includes` - Objects included by `object`, used to tell enums apart from classes
+    pub fn unwrap_expression(
+        &self,
+        value: &str,
+        class: Option<&str>,
+        object: &Object,
+        includes: &[Object],
+    ) -> String {
+        match self {
+            Type::Object(name) if includes.iter().any(|x| x.is_named(name) && x.is_enum()) => {
+                match class {
+                    Some(class) => {
+                        format!("({value} == null ? null : {value}.toOriginal({class}))")
+                    }
+                    None => format!("({value} == null ? null : {value}.unwrap())"),
+                }
+            }
+            Type::Object(_) | Type::SelfObject => {
+                format!("({value} == null ? null : {value}.unwrap())")
+            }
+            // Inside of collections there is no original array to take the class from
+            Type::Array(_) if self.has_unwrap_method() => format!(
+                "unwrap{}({value}, {})",
+                self.mangled_name(object),
+                class
+                    .map(String::from)
+                    .or_else(|| self.original_class(object, includes))
+                    .unwrap_or("null".to_string())
+            ),
+            _ if self.has_unwrap_method() => {
+                format!("unwrap{}({value})", self.mangled_name(object))
+            }
+            _ => value.to_string(),
+        }
+    }
+
+    /// Java expression of the class of the original values of this type, when it's known without
+    /// an original value. Objects need to be bound to their original class.
+    pub fn original_class(&self, object: &Object, includes: &[Object]) -> Option<String> {
+        let target = |target: &Object| {
+            target
+                .classes()
+                .next()
+                .map(|_| format!("{}Accessor.targetClass()", target.display_name()))
         };
 
-        if root && self.ends_in_object(object).is_none() {
-            format!("({cast})value")
-        } else {
-            cast
+        match self {
+            Type::SelfObject => target(object),
+            Type::Object(name) if object.is_named(name) => target(object),
+            Type::Object(name) => includes.iter().find(|x| x.is_named(name)).and_then(target),
+            Type::Array(inner) => Some(format!(
+                "java.lang.reflect.Array.newInstance({}, 0).getClass()",
+                inner.original_class(object, includes)?
+            )),
+            Type::List(_) => Some("java.util.List.class".to_string()),
+            Type::Set(_) => Some("java.util.Set.class".to_string()),
+            Type::Map(_, _) => Some("java.util.Map.class".to_string()),
+            Type::Optional(_) => Some("java.util.Optional.class".to_string()),
+            other => Some(format!("{}.class", other.java_name(object))),
+        }
+    }
+
+    /// Java expression turning `value`, an expression of static type `Object`, into this type.
+    pub fn access_expression(&self, value: &str, object: &Object) -> String {
+        match self {
+            Type::Object(name) => format!("{name}Accessor.access({value})"),
+            Type::SelfObject => format!("{}Accessor.access({value})", object.display_name()),
+            _ if self.has_access_method() => {
+                format!("access{}({value})", self.mangled_name(object))
+            }
+            Type::Array(_) => match self.ends_in_object(object) {
+                Some(name) => format!("{name}Accessor.accessArray((Object[]){value})"),
+                None => format!("({}){value}", self.java_name(object)),
+            },
+            other => format!("({}){value}", other.boxed_java_name(object)),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 struct Field {
     pub name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rename: Option<String>,
+    /// Other names the field can have, e.g. in other versions of an obfuscated library. Tried in
+    /// order after `name`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
     #[serde(rename = "type")]
     pub field_type: Type,
+    /// Searches the superclasses too, for inherited fields of any visibility
     #[serde(skip_serializing_if = "is_false", default)]
     pub hierarchy: bool,
+    /// Fully qualified name of the last superclass searched, when `hierarchy` is set. Without it
+    /// the lookup goes up to `Object`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_at: Option<String>,
+    #[serde(rename = "static", skip_serializing_if = "is_false", default)]
+    pub is_static: bool,
+    /// The field may not exist at runtime, in that case it keeps it's default value instead of
+    /// failing the whole access.
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub optional: bool,
 }
 
 impl Field {
     pub fn display_name(&self) -> &str {
         self.rename.as_ref().unwrap_or(&self.name)
     }
+
+    /// Java expression looking up the handle of the field in `class`, trying every candidate name.
+    /// Handles are cached by the generated lookup, so it's cheap to call on every access.
+    pub fn lookup(&self, class: &str, access: FieldAccess) -> String {
+        let names = std::iter::once(&self.name)
+            .chain(&self.aliases)
+            .map(|x| format!("\"{x}\""))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let function = match access {
+            FieldAccess::Reflection => "findField",
+            FieldAccess::Handles => "findVarHandle",
+        };
+
+        let stop_at = match &self.stop_at {
+            Some(ancestor) => format!("\"{ancestor}\""),
+            None => "null".to_string(),
+        };
+
+        format!(
+            "{function}({class}, new String[] {{ {names} }}, {}, {stop_at})",
+            self.hierarchy
+        )
+    }
+
+    /// Java expression reading the field of `instance` through the handle `field`. Static fields
+    /// ignore the instance.
+    pub fn read(&self, instance: &str, access: FieldAccess) -> String {
+        match (access, self.is_static) {
+            (FieldAccess::Reflection, false) => {
+                format!("field.{}({instance})", self.field_type.field_getter())
+            }
+            (FieldAccess::Reflection, true) => {
+                format!("field.{}(null)", self.field_type.field_getter())
+            }
+            (FieldAccess::Handles, false) => format!("field.get({instance})"),
+            (FieldAccess::Handles, true) => "field.get()".to_string(),
+        }
+    }
+
+    /// Java statement writing `value` into the field of `instance` through the handle `field`.
+    /// Static fields ignore the instance.
+    pub fn write(&self, instance: &str, value: &str, access: FieldAccess) -> String {
+        match (access, self.is_static) {
+            (FieldAccess::Reflection, false) => format!("field.set({instance}, {value});"),
+            (FieldAccess::Reflection, true) => format!("field.set(null, {value});"),
+            (FieldAccess::Handles, false) => format!("field.set({instance}, {value});"),
+            (FieldAccess::Handles, true) => format!("field.set({value});"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Method {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rename: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<Type>,
+    #[serde(default, rename = "returns", skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<Type>,
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub hierarchy: bool,
+    #[serde(rename = "static", skip_serializing_if = "is_false", default)]
+    pub is_static: bool,
+}
+
+impl Method {
+    pub fn display_name(&self) -> &str {
+        self.rename.as_ref().unwrap_or(&self.name)
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 struct Variant {
     pub name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -154,27 +652,159 @@ fn is_false(b: &bool) -> bool {
     !b
 }
 
+/// What is generated from the schemas
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+enum Backend {
+    /// Accessor classes that reach the original objects through reflection
+    #[default]
+    Accessor,
+    /// Mixin `@Accessor` and `@Invoker` interfaces, plus the mixin config listing them
+    Mixin,
+    /// Rust modules for native code, reading the original objects through cached JNI field IDs
+    Jni,
+}
+
+/// Language of the generated accessors
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+enum Lang {
+    #[default]
+    Java,
+    /// Kotlin classes with properties, reaching the original objects through reflection
+    Kotlin,
+}
+
+/// How the generated accessor reads the original object
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+enum Mode {
+    /// Every field is copied into the accessor when it's created
+    #[default]
+    Snapshot,
+    /// Only the original object is kept, fields are read through getters on every call
+    Live,
+}
+
+/// How the generated accessors read and write the fields of the original object
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+enum FieldAccess {
+    /// `java.lang.reflect.Field`
+    #[default]
+    Reflection,
+    /// `java.lang.invoke.VarHandle`, obtained through `MethodHandles.privateLookupIn`
+    Handles,
+}
+
+impl FieldAccess {
+    /// Java type of the handle a field is looked up into
+    pub fn handle_type(self) -> &'static str {
+        match self {
+            FieldAccess::Reflection => "java.lang.reflect.Field",
+            FieldAccess::Handles => "java.lang.invoke.VarHandle",
+        }
+    }
+
+    /// Java expression of the class of the values the handle `field` holds
+    pub fn value_class(self) -> &'static str {
+        match self {
+            FieldAccess::Reflection => "field.getType()",
+            FieldAccess::Handles => "field.varType()",
+        }
+    }
+}
+
 type Fields = Vec<Field>;
 type Variants = Vec<Variant>;
+type Methods = Vec<Method>;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 struct Object {
     pub name: String,
     #[serde(default)]
     pub rename: Option<String>,
     pub package: String,
+    /// Fully qualified name of the original class, needed to reach static members
+    #[serde(default, rename = "class", skip_serializing_if = "Option::is_none")]
+    pub class_name: Option<String>,
+    /// Other names the original class can have, e.g. in other versions of the library
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternate_classes: Vec<String>,
+    /// Overrides the mode selected for the whole compilation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<Mode>,
+    /// Selected for the whole compilation
+    #[serde(skip)]
+    pub field_access: FieldAccess,
+    /// Name of the object whose accessor this one extends, inheriting its fields. Resolved like
+    /// the objects referenced by fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// Paths of schemas to include, relative to this one. Referenced objects are found by name in
+    /// the project, includes only override them, e.g. with schemas from outside of it.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub includes: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub variants: Variants,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub fields: Fields,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Methods,
 }
 
 impl Object {
     pub fn display_name(&self) -> &str {
         self.rename.as_ref().unwrap_or(&self.name)
     }
+
+    /// Name of the object qualified with its package, `package.Name`
+    pub fn qualified_name(&self) -> String {
+        format!("{}.{}", self.package, self.display_name())
+    }
+
+    /// Whether a type reference, plain or qualified, names this object
+    pub fn is_named(&self, name: &str) -> bool {
+        self.display_name() == name || self.qualified_name() == name
+    }
+
+    pub fn is_enum(&self) -> bool {
+        !self.variants.is_empty()
+    }
+
+    /// Every fully qualified name the original class can have, in order of preference.
+    pub fn classes(&self) -> impl Iterator<Item = &String> {
+        self.class_name.iter().chain(&self.alternate_classes)
+    }
+
+    /// Java expression of the class the members of `instance` are looked up in.
+    /// When the object is bound to an original class the instance is checked against it, and the
+    /// members are resolved on that class even if the instance is a subclass.
+    pub fn instance_class(&self, instance: &str) -> String {
+        if self.classes().next().is_some() {
+            format!("matchTargetClass({instance}.getClass())")
+        } else {
+            format!("{instance}.getClass()")
+        }
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.mode == Some(Mode::Live)
+    }
+
+    /// Fields read from an instance of the object
+    pub fn instance_fields(&self) -> impl Iterator<Item = &Field> {
+        self.fields.iter().filter(|x| !x.is_static)
+    }
+
+    pub fn static_fields(&self) -> impl Iterator<Item = &Field> {
+        self.fields.iter().filter(|x| x.is_static)
+    }
+
+    /// Every type used by the object, in fields and method signatures.
+    pub fn types(&self) -> impl Iterator<Item = &Type> {
+        self.fields.iter().map(|x| &x.field_type).chain(
+            self.methods
+                .iter()
+                .flat_map(|x| x.parameters.iter().chain(x.return_type.as_ref())),
+        )
+    }
 }
 
 #[cfg(debug_assertions)]
@@ -182,6 +812,8 @@ fn setup_logging() {
     tracing_subscriber::fmt::fmt()
         .pretty()
         .with_max_level(tracing::Level::TRACE)
+        // Stdout only has the diagnostics, which tools can read as JSON
+        .with_writer(std::io::stderr)
         .init();
 }
 
@@ -190,60 +822,164 @@ fn setup_logging() {
     tracing_subscriber::fmt::fmt()
         .pretty()
         .with_max_level(tracing::Level::WARN)
+        // Stdout only has the diagnostics, which tools can read as JSON
+        .with_writer(std::io::stderr)
         .init();
 }
 
-fn main() -> anyhow::Result<()> {
+fn main() -> ExitCode {
     let args = cli::Arguments::parse();
     setup_logging();
+    let message_format = args.message_format;
 
-    match args.command {
+    let failed = match args.command {
         cli::Command::Format { directory } => {
             use colored::*;
-            let (changes, _errors) = format(&directory)?;
-            if changes == 0 {
-                println!("All files checked - {}", "No changes were made".green())
-            } else {
-                println!(
-                    "All files checked - {} {} {}",
-                    "Modified".yellow(),
-                    changes.to_string().yellow(),
-                    "files".yellow()
-                );
+            let (changes, errors) = format(&directory, message_format);
+            if message_format == MessageFormat::Human {
+                if changes == 0 {
+                    println!("All files checked - {}", "No changes were made".green())
+                } else {
+                    println!(
+                        "All files checked - {} {} {}",
+                        "Modified".yellow(),
+                        changes.to_string().yellow(),
+                        "files".yellow()
+                    );
+                }
             }
+            errors > 0
         }
-        cli::Command::Compile { input, output } => match compiler::compile()
-            .target(input)
-            .output(output)
-            .call()
-        {
-            Ok(files) => {
+        cli::Command::Compile {
+            input,
+            output,
+            options,
+        } => match compile(input, output, &options, message_format) {
+            Some(files) => {
+                let mut failed = false;
                 for file in files {
                     if let Err(err) = std::fs::write(&file.path, file.content) {
-                        tracing::error!("Error writing to file {}: {}", file.path.display(), err);
+                        let err = compiler::SourceError::IO(err);
+                        diagnostics::emit(message_format, Severity::Error, &file.path, &err, None);
+                        failed = true;
                     }
                 }
+                failed
             }
-            Err(errors) => {
-                for error in errors {
-                    println!("{}: {}", error.0.display(), error.1);
+            None => true,
+        },
+        cli::Command::Check { input, options } => {
+            // Nothing is written, the output only places the generated files
+            match compile(input, PathBuf::new(), &options, message_format) {
+                Some(files) => {
+                    use colored::*;
+                    if message_format == MessageFormat::Human {
+                        println!(
+                            "All files checked - {} {} {}",
+                            "Would generate".green(),
+                            files.len().to_string().green(),
+                            "files".green()
+                        );
+                    }
+                    false
                 }
+                None => true,
+            }
+        }
+    };
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Loads the mappings and compiles the schemas in `input`, emitting the errors and warnings.
+/// None when anything failed.
+fn compile(
+    input: PathBuf,
+    output: PathBuf,
+    options: &cli::CompileOptions,
+    message_format: MessageFormat,
+) -> Option<Vec<compiler::JavaSource>> {
+    let mappings = match &options.mappings {
+        Some(path) => match compiler::Mappings::load(path) {
+            Ok(x) => Some(x),
+            Err(err) => {
+                diagnostics::emit(message_format, Severity::Error, path, &err, None);
+                return None;
             }
         },
+        None => None,
+    };
+
+    let mut warnings = vec![];
+    let compiled = compiler::compile()
+        .target(input)
+        .output(output)
+        .maybe_mappings(mappings.as_ref())
+        .mode(options.mode)
+        .field_access(options.field_access)
+        .backend(options.backend)
+        .lang(options.lang)
+        .warnings(&mut warnings)
+        .call();
+
+    report(warnings, Severity::Warning, message_format);
+    match compiled {
+        Ok(files) => Some(files),
+        Err(errors) => {
+            report(errors, Severity::Error, message_format);
+            None
+        }
     }
+}
 
-    Ok(())
+/// Emits the errors or warnings of a compilation, with the source of the schemas they are about
+fn report(
+    errors: Vec<(PathBuf, compiler::SourceError)>,
+    severity: Severity,
+    message_format: MessageFormat,
+) {
+    for (path, error) in errors {
+        let source = std::fs::read_to_string(&path).ok();
+        diagnostics::emit(message_format, severity, &path, &error, source.as_deref());
+    }
 }
 
-fn format(dir: &Path) -> anyhow::Result<(i32, i32)> {
+/// Formats every schema in `dir` recursively, the files that fail are emitted and skipped.
+/// Returns the number of changed files and the number of errors.
+fn format(dir: &Path, message_format: MessageFormat) -> (i32, i32) {
+    use compiler::SourceError;
+
     let mut changes = 0;
     let mut errors = 0;
+    let fail = |path: &Path, err: SourceError, source: Option<&str>| {
+        diagnostics::emit(message_format, Severity::Error, path, &err, source);
+    };
 
-    for path in dir.read_dir()? {
-        let path = path?.path();
+    let read_dir = match dir.read_dir() {
+        Ok(x) => x,
+        Err(err) => {
+            fail(dir, SourceError::IO(err), None);
+            errors += 1;
+            return (changes, errors);
+        }
+    };
+
+    for path in read_dir {
+        let path = match path {
+            Ok(x) => x.path(),
+            Err(err) => {
+                fail(dir, SourceError::IO(err), None);
+                errors += 1;
+                continue;
+            }
+        };
 
         if path.is_dir() {
-            let (c, e) = format(&path)?;
+            let (c, e) = format(&path, message_format);
             changes += c;
             errors += e;
         }
@@ -252,32 +988,49 @@ fn format(dir: &Path) -> anyhow::Result<(i32, i32)> {
             continue;
         }
 
-        let current_content = std::fs::read_to_string(&path)?;
-        let current: Result<Object, _> = ron::from_str(&current_content);
-        let Ok(current) = current else {
-            use colored::*;
-            println!(
-                "{}: {} {}",
-                "ERROR".red(),
-                path.display(),
-                current.unwrap_err()
-            );
-            errors += 1;
-            continue;
+        let current_content = match std::fs::read_to_string(&path) {
+            Ok(x) => x,
+            Err(err) => {
+                fail(&path, SourceError::IO(err), None);
+                errors += 1;
+                continue;
+            }
+        };
+        let current = match ron::from_str::<Object>(&current_content) {
+            Ok(x) => x,
+            Err(err) => {
+                let err = SourceError::Deserialization(err);
+                fail(&path, err, Some(&current_content));
+                errors += 1;
+                continue;
+            }
         };
 
-        let expected = ron::ser::to_string_pretty(&current, Default::default())?;
+        let expected = match ron::ser::to_string_pretty(&current, Default::default()) {
+            Ok(x) => x,
+            Err(err) => {
+                fail(&path, SourceError::Serialization(err), None);
+                errors += 1;
+                continue;
+            }
+        };
 
         if expected != current_content {
             use colored::*;
+            if let Err(err) = std::fs::write(&path, expected) {
+                fail(&path, SourceError::IO(err), None);
+                errors += 1;
+                continue;
+            }
             changes += 1;
-            std::fs::write(&path, expected)?;
-            println!("{}: {}", "FORMATTED".yellow(), path.display());
-        } else {
+            if message_format == MessageFormat::Human {
+                println!("{}: {}", "FORMATTED".yellow(), path.display());
+            }
+        } else if message_format == MessageFormat::Human {
             use colored::*;
             println!("{}: {}", "OK".green(), path.display());
         }
     }
 
-    Ok((changes, errors))
+    (changes, errors)
 }